@@ -0,0 +1,95 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Generates `$OUT_DIR` sources from solution folders: example tests, and embedded inputs (only with `embed-inputs` feature)
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let solutions_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/solutions");
+    println!("cargo:rerun-if-changed={}", solutions_dir.display());
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let days = solution_days(&solutions_dir);
+
+    generate_example_tests(&days, &out_dir.join("examples.rs"));
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        embed_inputs(&days, &out_dir.join("inputs.rs"));
+    }
+}
+
+/// Writes one test per case of each day `examples.json` file (e.g: `y2022_day09_example_2`)
+fn generate_example_tests(days: &[(u16, String, PathBuf)], target: &Path) {
+    let mut tests: Vec<String> = vec![];
+    for (year, day, day_dir) in days {
+        let examples_file = day_dir.join("examples.json");
+        if !examples_file.is_file() {
+            continue;
+        }
+
+        // Invalid file is reported by a single failing test, instead of failing the whole build
+        let nb_examples = match fs::read_to_string(&examples_file).ok().and_then(|data| serde_json::from_str::<Vec<serde_json::Value>>(&data).ok()) {
+            Some(examples) => examples.len(),
+            None => {
+                tests.push(format!("#[test]\nfn y{year}_{day}_examples() {{\n    read_day_examples({year}, {day:?});\n}}\n"));
+                continue;
+            }
+        };
+
+        for idx in 0..nb_examples {
+            tests.push(format!("#[test]\nfn y{year}_{day}_example_{}() {{\n    run_example({year}, {day:?}, {idx});\n}}\n", idx + 1));
+        }
+    }
+
+    fs::write(target, tests.join("\n")).unwrap();
+}
+
+/// Writes every day `input.txt` file as embedded input (missing ones are skipped with a warning)
+fn embed_inputs(days: &[(u16, String, PathBuf)], target: &Path) {
+    let mut entries: Vec<String> = vec![];
+    for (year, day, day_dir) in days {
+        let input_file = day_dir.join("input.txt");
+        if input_file.is_file() {
+            entries.push(format!("    ({year}, {day:?}, include_str!({:?}))", input_file.display().to_string()));
+        }
+        else {
+            println!("cargo:warning=Missing input file \"{}\", {year} {day} input not embedded", input_file.display());
+        }
+    }
+
+    let source = format!(
+        "/// Inputs embedded at build time, as (year, day, input)\npub const EMBEDDED_INPUTS: &[(u16, &str, &str)] = &[\n{}\n];\n",
+        entries.join(",\n")
+    );
+    fs::write(target, source).unwrap();
+}
+
+/// Solution modules, as (year, day, folder), ordered by year and day
+fn solution_days(solutions_dir: &Path) -> Vec<(u16, String, PathBuf)> {
+    let mut days: Vec<(u16, String, PathBuf)> = vec![];
+    for (year, year_dir) in sub_dirs(solutions_dir, "y") {
+        for (day, day_dir) in sub_dirs(&year_dir, "day") {
+            if day_dir.join("mod.rs").is_file() {
+                days.push((year[1..].parse::<u16>().unwrap(), day, day_dir));
+            }
+        }
+    }
+
+    days
+}
+
+/// Sub folders with given name prefix followed by a number (e.g: `y2022` or `day05`), ordered by name
+fn sub_dirs(dir: &Path, prefix: &str) -> Vec<(String, PathBuf)> {
+    let mut dirs: Vec<(String, PathBuf)> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_dir())
+            .filter_map(|path| path.file_name().and_then(|n| n.to_str()).map(String::from).map(|name| (name, path)))
+            .filter(|(name, _)| matches!(name.strip_prefix(prefix).map(|number| number.parse::<u16>()), Some(Ok(_))))
+            .collect(),
+        Err(_) => vec![]
+    };
+
+    dirs.sort();
+    dirs
+}
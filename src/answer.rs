@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+use crate::parsing::ParseError;
+
+/// Name of the file holding accepted answers, next to day input file
+pub const ANSWERS_FILE: &str = "answers.json";
+
+/// A solution answer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    Empty
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Answer::Number(n) => write!(fmt, "{n}"),
+            Answer::Text(s) => write!(fmt, "{s}"),
+            Answer::Empty => write!(fmt, "-")
+        }
+    }
+}
+
+/// A solution failure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// Input could not be parsed
+    Parse(ParseError),
+    /// Input was read, but no answer could be found (e.g: search space exhausted)
+    NoSolution(String),
+    /// Solution parameter value could not be parsed
+    InvalidParam(String)
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            SolveError::Parse(error) => write!(fmt, "{error}"),
+            SolveError::NoSolution(reason) => write!(fmt, "No solution found: {reason}"),
+            SolveError::InvalidParam(reason) => write!(fmt, "{reason}")
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+impl From<ParseError> for SolveError {
+    fn from(error: ParseError) -> SolveError {
+        SolveError::Parse(error)
+    }
+}
+
+/// Conversions from numbers returned by solutions
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Number(n as i64)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+// Only comparing with i64, so that integer literals (e.g: `assert_eq!(24000, answer)`) are inferred without ambiguity
+impl PartialEq<Answer> for i64 {
+    fn eq(&self, other: &Answer) -> bool {
+        other == &Answer::Number(*self)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(String::from(s))
+    }
+}
+
+impl From<()> for Answer {
+    fn from(_: ()) -> Answer {
+        Answer::Empty
+    }
+}
+
+impl PartialEq<Answer> for String {
+    fn eq(&self, other: &Answer) -> bool {
+        matches!(other, Answer::Text(s) if s == self)
+    }
+}
+
+impl PartialEq<Answer> for &str {
+    fn eq(&self, other: &Answer) -> bool {
+        matches!(other, Answer::Text(s) if s == self)
+    }
+}
+
+impl PartialEq<Answer> for () {
+    fn eq(&self, other: &Answer) -> bool {
+        other == &Answer::Empty
+    }
+}
+
+/// Read accepted answers file (e.g: `{ "solution1": 24000, "solution2": "CMZ" }`), if any
+pub fn read_expected_answers(filename: &Path) -> Result<Option<HashMap<String, Answer>>, Box<dyn Error>> {
+    if !filename.is_file() {
+        return Ok(None);
+    }
+
+    let data = crate::read_file(filename)?;
+    match serde_json::from_str(&data) {
+        Ok(answers) => Ok(Some(answers)),
+        Err(err) => Err(format!("Invalid answers file \"{}\": {err}", filename.display()).into())
+    }
+}
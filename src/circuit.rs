@@ -1,3 +1,5 @@
+use crate::parsing::{parse_value, ParseError};
+
 /// Type of instructions for clock circuit program
 #[derive(Debug)]
 pub enum InstructionType {
@@ -13,19 +15,21 @@ pub struct Instruction {
 }
 
 /// Method to read instructions for clock circuit program
-pub fn read_instructions(data: String) -> Vec<Instruction> {
+pub fn read_instructions(data: &str) -> Result<Vec<Instruction>, ParseError> {
     data.lines()
-        .map(|l| {
+        .enumerate()
+        .map(|(idx, l)| {
             let mut line_iter = l.split_whitespace();
-            let instruction_name = line_iter.next().unwrap();
+            let instruction_name = line_iter.next().ok_or_else(|| ParseError::at_line(idx, l, "Missing instruction"))?;
 
             match instruction_name {
-                "noop" => Instruction { kind: InstructionType::Noop, value: None },
+                "noop" => Ok(Instruction { kind: InstructionType::Noop, value: None }),
                 "addx" => {
-                    let instruction_value = line_iter.next().unwrap().parse::<isize>().unwrap();
-                    Instruction { kind: InstructionType::AddX, value: Some(instruction_value) }
+                    let value_text = line_iter.next().ok_or_else(|| ParseError::at_end(idx, l, "Missing addx value"))?;
+                    let instruction_value = parse_value::<isize>(idx, l, value_text)?;
+                    Ok(Instruction { kind: InstructionType::AddX, value: Some(instruction_value) })
                 },
-                _ => panic!("Invalid instruction: {l}")
+                _ => Err(ParseError::at_text(idx, l, instruction_name, &format!("Invalid instruction \"{instruction_name}\"")))
             }
         })
         .collect()
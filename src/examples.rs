@@ -0,0 +1,97 @@
+//! Example cases declared in each day `examples.json` file, every case being run as a test (generated by build.rs)
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+use crate::answer::Answer;
+use crate::params::Params;
+
+/// Name of the file declaring example cases, next to day example files
+pub const EXAMPLES_FILE: &str = "examples.json";
+
+/// An example case: solution part run against day example file, with given parameters
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Example {
+    pub file: String,
+    pub part: u8,
+    #[serde(default)]
+    pub params: HashMap<String, serde_json::Value>,
+    pub expected: Answer
+}
+
+impl Example {
+    /// Solution parameters (e.g: `"params": { "y": 10 }`)
+    pub fn params(&self) -> Params {
+        let mut params = Params::new();
+        for (key, value) in self.params.iter() {
+            match value {
+                serde_json::Value::String(text) => params.set(key, text),
+                value => params.set(key, value)
+            }
+        }
+
+        params
+    }
+}
+
+/// Read example cases file (e.g: `[{ "file": "test2.txt", "part": 2, "expected": 36 }]`), if any
+pub fn read_examples(filename: &Path) -> Result<Option<Vec<Example>>, Box<dyn Error>> {
+    if !filename.is_file() {
+        return Ok(None);
+    }
+
+    let data = crate::read_file(filename)?;
+    match serde_json::from_str(&data) {
+        Ok(examples) => Ok(Some(examples)),
+        Err(err) => Err(format!("Invalid examples file \"{}\": {err}", filename.display()).into())
+    }
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions;
+    use std::path::PathBuf;
+
+    fn day_dir(year: u16, day: &str) -> PathBuf {
+        crate::year_dir(Path::new(env!("CARGO_MANIFEST_DIR")), year).join(day)
+    }
+
+    fn read_day_examples(year: u16, day: &str) -> Vec<Example> {
+        read_examples(&day_dir(year, day).join(EXAMPLES_FILE)).unwrap().unwrap_or_default()
+    }
+
+    fn run_example(year: u16, day: &str, idx: usize) {
+        let example = &read_day_examples(year, day)[idx];
+        let solution = match solutions::find_solution(year, day, &example.part.to_string()) {
+            Some(solution) => solution,
+            None => panic!("Unknown solution{} for {year} {day}", example.part)
+        };
+
+        let data = crate::read_file(day_dir(year, day).join(&example.file)).unwrap();
+        let answer = (solution.run)(&data, &example.params());
+        assert_eq!(Ok(example.expected.clone()), answer, "{year} {day} solution{} with \"{}\" example", example.part, example.file);
+    }
+
+    // One test per example case (e.g: `y2022_day09_example_2`)
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+    #[test]
+    fn test_read_examples() {
+        let examples: Vec<Example> = serde_json::from_str(
+            r#"[{ "file": "test.txt", "part": 1, "params": { "y": 10, "name": "abc" }, "expected": 26 }, { "file": "test.txt", "part": 2, "expected": "CMZ" }]"#
+        )
+        .unwrap();
+
+        assert_eq!(Answer::Number(26), examples[0].expected);
+        assert_eq!(Ok(10), examples[0].params().get::<isize>("y", 0));
+        assert_eq!(Ok(String::from("abc")), examples[0].params().get::<String>("name", String::new()));
+        assert!(examples[1].params().is_empty());
+        assert_eq!(Answer::Text(String::from("CMZ")), examples[1].expected);
+        assert!(read_examples(Path::new("./missing.json")).unwrap().is_none());
+    }
+}
@@ -0,0 +1,530 @@
+//! Grid storage and topology shared by 2d, 3d and custom grids, with path searches working on any of them
+use crate::trace;
+use std::cell::Cell;
+use std::cmp::Reverse;
+use std::collections::hash_map::{self, HashMap};
+use std::collections::{BinaryHeap, HashSet};
+use std::hash::Hash;
+
+/// A map of values by location, with its own topology (which locations are adjacent to one another)
+pub trait GridLike {
+    type Location: Copy + Ord + Hash;
+    type Value: Eq;
+
+    fn size(&self) -> usize;
+
+    fn add_location(&mut self, location: Self::Location, value: Self::Value) -> Option<Self::Value>;
+
+    fn remove_location(&mut self, location: &Self::Location) -> Option<Self::Value>;
+
+    fn get_value(&self, location: &Self::Location) -> Option<&Self::Value>;
+
+    fn get_value_mut(&mut self, location: &Self::Location) -> Option<&mut Self::Value>;
+
+    /// Every location with its value, in no particular order
+    fn locations(&self) -> Box<dyn Iterator<Item = (Self::Location, &Self::Value)> + '_>;
+
+    /// Keep only locations for which closure returns true
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&Self::Location, &mut Self::Value) -> bool;
+
+    /// Locations adjacent to location (e.g: 4 orthogonal locations in 2d, 6 in 3d), whether they are in grid or not
+    fn neighbors(&self, location: &Self::Location) -> Vec<Self::Location>;
+
+    fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    fn contains_location(&self, location: &Self::Location) -> bool {
+        self.get_value(location).is_some()
+    }
+
+    fn keep_only_matching_locations(&mut self, f: &dyn Fn(&Self::Location, &mut Self::Value) -> bool) {
+        self.retain(f);
+    }
+
+    fn count_values(&self, value: &Self::Value) -> usize {
+        self.locations().filter(|(_, v)| *v == value).count()
+    }
+
+    /// Locations with value, ordered by location
+    fn get_locations_with_value(&self, value: &Self::Value) -> Vec<Self::Location> {
+        self.get_locations_with_values(vec![value])
+    }
+
+    /// Locations with any of the values, ordered by location
+    fn get_locations_with_values(&self, values: Vec<&Self::Value>) -> Vec<Self::Location> {
+        let mut locations: Vec<Self::Location> = self.locations().filter(|(_, v)| values.contains(v)).map(|(l, _)| l).collect();
+        locations.sort();
+        locations
+    }
+
+    /// Locations with value, in no particular order
+    fn get_mapped_locations_with_value(&self, value: &Self::Value) -> Vec<Self::Location> {
+        self.locations().filter(|(_, v)| *v == value).map(|(l, _)| l).collect()
+    }
+}
+
+/// Smallest area containing locations (e.g: 2d rectangle or 3d box)
+pub trait Bounds<L>: Copy {
+    /// Bounds of a single location
+    fn new(location: L) -> Self;
+
+    /// Smallest bounds containing both bounds and location
+    fn extend(&self, location: &L) -> Self;
+
+    /// Whether location is on one of the bounds sides (e.g: removing it may shrink bounds)
+    fn is_on_border(&self, location: &L) -> bool;
+}
+
+/// Hash map of locations keeping track of their bounds as locations are added (and recomputing them on demand after locations are
+/// removed), shared by 2d and 3d grids
+pub(crate) struct BoundedMap<L, V, B> {
+    locations: HashMap<L, V>,
+    bounds: Cell<Option<B>>,
+    outdated_bounds: Cell<bool>
+}
+
+impl<L, V, B> BoundedMap<L, V, B>
+where
+    L: Copy + Eq + Hash,
+    B: Bounds<L>
+{
+    pub(crate) fn new() -> BoundedMap<L, V, B> {
+        BoundedMap { locations: HashMap::new(), bounds: Cell::new(None), outdated_bounds: Cell::new(false) }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.locations.len()
+    }
+
+    pub(crate) fn get(&self, location: &L) -> Option<&V> {
+        self.locations.get(location)
+    }
+
+    pub(crate) fn get_mut(&mut self, location: &L) -> Option<&mut V> {
+        self.locations.get_mut(location)
+    }
+
+    pub(crate) fn iter(&self) -> hash_map::Iter<'_, L, V> {
+        self.locations.iter()
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> hash_map::IterMut<'_, L, V> {
+        self.locations.iter_mut()
+    }
+
+    pub(crate) fn insert(&mut self, location: L, value: V) -> Option<V> {
+        if !self.outdated_bounds.get() {
+            self.bounds.set(Some(match self.bounds.get() {
+                Some(bounds) => bounds.extend(&location),
+                None => B::new(location)
+            }));
+        }
+
+        self.locations.insert(location, value)
+    }
+
+    pub(crate) fn remove(&mut self, location: &L) -> Option<V> {
+        let value = self.locations.remove(location);
+        if value.is_some() && self.bounds.get().map(|b| b.is_on_border(location)).unwrap_or(true) {
+            self.outdated_bounds.set(true);
+        }
+
+        value
+    }
+
+    pub(crate) fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&L, &mut V) -> bool
+    {
+        let size = self.locations.len();
+        self.locations.retain(f);
+
+        if self.locations.len() != size {
+            self.outdated_bounds.set(true);
+        }
+    }
+
+    /// Location entry for in-place update (bounds are recomputed on demand if location was not in map yet)
+    pub(crate) fn entry(&mut self, location: L) -> hash_map::Entry<'_, L, V> {
+        if !self.locations.contains_key(&location) {
+            self.outdated_bounds.set(true);
+        }
+
+        self.locations.entry(location)
+    }
+
+    /// Smallest bounds containing every location (`None` for empty map)
+    pub(crate) fn bounds(&self) -> Option<B> {
+        if self.outdated_bounds.replace(false) {
+            self.bounds.set(self.locations.keys().fold(None, |bounds, location| match bounds {
+                Some(bounds) => Some(bounds.extend(location)),
+                None => Some(B::new(*location))
+            }));
+        }
+
+        self.bounds.get()
+    }
+}
+
+impl<L, V, B> Clone for BoundedMap<L, V, B>
+where
+    L: Clone,
+    V: Clone,
+    B: Copy
+{
+    fn clone(&self) -> BoundedMap<L, V, B> {
+        BoundedMap { locations: self.locations.clone(), bounds: self.bounds.clone(), outdated_bounds: self.outdated_bounds.clone() }
+    }
+}
+
+impl<L, V, B> IntoIterator for BoundedMap<L, V, B> {
+    type Item = (L, V);
+    type IntoIter = hash_map::IntoIter<L, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.locations.into_iter()
+    }
+}
+
+/// Validation of a move from current location value to next location value
+pub type LocationValidator<V> = Box<dyn Fn(&V, &V) -> bool>;
+
+/// Cost of a move from current location value to next location value (`None` if move is not allowed)
+pub type MoveCost<V> = Box<dyn Fn(&V, &V) -> Option<usize>>;
+
+/// A path of locations
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct Path<L> {
+    pub locations: Vec<L>
+}
+
+impl<L> Path<L>
+where
+    L: Copy
+{
+    pub fn new(location: &L) -> Path<L> {
+        Path { locations: vec![*location] }
+    }
+
+    pub fn from(path: &Path<L>) -> Path<L> {
+        Path { locations: path.locations.clone() }
+    }
+}
+
+/// A Path target location or value
+pub enum PathTarget<L, V> {
+    Location(L),
+    Value(V)
+}
+
+impl<L, V> PathTarget<L, V>
+where
+    L: Eq,
+    V: Eq
+{
+    fn is_reached(&self, location: &L, value: &V) -> bool {
+        match self {
+            PathTarget::Location(goal_location) => location == goal_location,
+            PathTarget::Value(goal_value) => value == goal_value
+        }
+    }
+}
+
+/// Neighbors of location inside map, with their value
+fn next_locations<'a, G>(map: &'a G, location: &G::Location) -> impl Iterator<Item = (G::Location, &'a G::Value)>
+where
+    G: GridLike
+{
+    map.neighbors(location).into_iter().filter_map(|l| map.get_value(&l).map(|v| (l, v)))
+}
+
+/// Find first shortest path found between start location and path target (breadth first search)
+pub fn find_shortest_path<G>(
+    map: &G,
+    start: &G::Location,
+    goal: &PathTarget<G::Location, G::Value>,
+    location_validator: Option<LocationValidator<G::Value>>
+) -> Option<Path<G::Location>>
+where
+    G: GridLike
+{
+    let mut visited_locations: HashSet<G::Location> = HashSet::new();
+    let mut paths_to_explored: Vec<Path<G::Location>> = vec![Path::new(start)];
+
+    let mut path_size = 0;
+    while !paths_to_explored.is_empty() {
+        path_size += 1;
+        trace!("Path size: {path_size}");
+
+        let current_paths: Vec<Path<G::Location>> = std::mem::take(&mut paths_to_explored);
+
+        for path in current_paths {
+            let current_location = path.locations.last().unwrap();
+            let current_value = map.get_value(current_location).unwrap();
+
+            for (location, value) in next_locations(map, current_location) {
+                // Removing already visited and invalid locations
+                if visited_locations.contains(&location) || !location_validator.as_ref().map(|validator| validator(current_value, value)).unwrap_or(true) {
+                    continue;
+                }
+
+                let mut next_path = Path::from(&path);
+                next_path.locations.push(location);
+
+                if goal.is_reached(&location, value) {
+                    // Found path to goal
+                    return Some(next_path);
+                }
+
+                visited_locations.insert(location);
+                paths_to_explored.push(next_path);
+            }
+        }
+    }
+
+    None
+}
+
+/// Find lowest cost path between start location and path target (Dijkstra), with its total cost
+pub fn find_lowest_cost_path<G>(
+    map: &G,
+    start: &G::Location,
+    goal: &PathTarget<G::Location, G::Value>,
+    move_cost: MoveCost<G::Value>
+) -> Option<(usize, Path<G::Location>)>
+where
+    G: GridLike
+{
+    let mut costs: HashMap<G::Location, usize> = HashMap::from([(*start, 0)]);
+    let mut previous_locations: HashMap<G::Location, G::Location> = HashMap::new();
+    let mut locations_to_explore = BinaryHeap::from([Reverse((0, *start))]);
+
+    while let Some(Reverse((cost, location))) = locations_to_explore.pop() {
+        let value = map.get_value(&location)?;
+        if costs.get(&location).map(|c| cost > *c).unwrap_or(false) {
+            continue; // Already reached with lower cost
+        }
+
+        if location != *start && goal.is_reached(&location, value) {
+            // Rebuilding path back to start
+            let mut locations = vec![location];
+            while let Some(previous_location) = previous_locations.get(locations.last().unwrap()) {
+                locations.push(*previous_location);
+            }
+            locations.reverse();
+
+            return Some((cost, Path { locations }));
+        }
+
+        for (next_location, next_value) in next_locations(map, &location) {
+            let next_cost = match move_cost(value, next_value) {
+                Some(move_cost) => cost + move_cost,
+                None => continue
+            };
+
+            if costs.get(&next_location).map(|c| next_cost < *c).unwrap_or(true) {
+                costs.insert(next_location, next_cost);
+                previous_locations.insert(next_location, location);
+                locations_to_explore.push(Reverse((next_cost, next_location)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Every location reachable from start location (included)
+pub fn flood_fill<G>(map: &G, start: &G::Location, location_validator: Option<LocationValidator<G::Value>>) -> HashSet<G::Location>
+where
+    G: GridLike
+{
+    let mut reached_locations: HashSet<G::Location> = HashSet::new();
+    if map.get_value(start).is_none() {
+        return reached_locations;
+    }
+
+    let mut locations_to_explore = vec![*start];
+    reached_locations.insert(*start);
+
+    while let Some(location) = locations_to_explore.pop() {
+        let value = map.get_value(&location).unwrap();
+
+        for (next_location, next_value) in next_locations(map, &location) {
+            if !reached_locations.contains(&next_location) && location_validator.as_ref().map(|validator| validator(value, next_value)).unwrap_or(true) {
+                reached_locations.insert(next_location);
+                locations_to_explore.push(next_location);
+            }
+        }
+    }
+
+    reached_locations
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Custom topology: a ring of locations where last location is adjacent to first one (removed locations leaving a gap in ring)
+    struct Ring {
+        values: Vec<Option<char>>
+    }
+
+    impl GridLike for Ring {
+        type Location = usize;
+        type Value = char;
+
+        fn size(&self) -> usize {
+            self.values.iter().filter(|v| v.is_some()).count()
+        }
+
+        fn add_location(&mut self, location: usize, value: char) -> Option<char> {
+            self.values[location].replace(value)
+        }
+
+        fn remove_location(&mut self, location: &usize) -> Option<char> {
+            self.values.get_mut(*location).and_then(|v| v.take())
+        }
+
+        fn get_value(&self, location: &usize) -> Option<&char> {
+            self.values.get(*location).and_then(|v| v.as_ref())
+        }
+
+        fn get_value_mut(&mut self, location: &usize) -> Option<&mut char> {
+            self.values.get_mut(*location).and_then(|v| v.as_mut())
+        }
+
+        fn locations(&self) -> Box<dyn Iterator<Item = (usize, &char)> + '_> {
+            Box::new(self.values.iter().enumerate().filter_map(|(l, v)| v.as_ref().map(|v| (l, v))))
+        }
+
+        fn retain<F>(&mut self, mut f: F)
+        where
+            F: FnMut(&usize, &mut char) -> bool
+        {
+            for (location, value) in self.values.iter_mut().enumerate() {
+                if value.as_mut().is_some_and(|v| !f(&location, v)) {
+                    *value = None;
+                }
+            }
+        }
+
+        fn neighbors(&self, location: &usize) -> Vec<usize> {
+            vec![(location + self.values.len() - 1) % self.values.len(), (location + 1) % self.values.len()]
+        }
+    }
+
+    fn ring() -> Ring {
+        Ring { values: "S.#..~~~E.".chars().map(Some).collect() }
+    }
+
+    /// 1d bounds: first and last location
+    impl Bounds<usize> for (usize, usize) {
+        fn new(location: usize) -> (usize, usize) {
+            (location, location)
+        }
+
+        fn extend(&self, location: &usize) -> (usize, usize) {
+            (self.0.min(*location), self.1.max(*location))
+        }
+
+        fn is_on_border(&self, location: &usize) -> bool {
+            *location == self.0 || *location == self.1
+        }
+    }
+
+    #[test]
+    fn test_bounded_map() {
+        let mut map: BoundedMap<usize, char, (usize, usize)> = BoundedMap::new();
+        assert_eq!(None, map.bounds());
+
+        map.insert(5, 'a');
+        map.insert(2, 'b');
+        map.insert(8, 'c');
+        assert_eq!(Some((2, 8)), map.bounds());
+
+        // Removing inner location keeps bounds, removing border one shrinks them
+        map.insert(4, 'd');
+        assert_eq!(Some('d'), map.remove(&4));
+        assert_eq!(Some((2, 8)), map.bounds());
+        assert_eq!(Some('c'), map.remove(&8));
+        assert_eq!(Some((2, 5)), map.bounds());
+
+        *map.entry(9).or_default() = 'e';
+        assert_eq!(Some((2, 9)), map.bounds());
+        map.retain(|_, value| *value != 'b');
+        assert_eq!(Some((5, 9)), map.bounds());
+        map.retain(|_, _| false);
+        assert_eq!((0, None), (map.len(), map.bounds()));
+    }
+
+    #[test]
+    fn test_find_shortest_path() {
+        let ring = ring();
+
+        // Wrapping around ring, as wall is in the way
+        let path = find_shortest_path(&ring, &0, &PathTarget::Value('E'), Some(Box::new(|_, next| *next != '#')));
+        assert_eq!(Some(vec![0, 9, 8]), path.map(|p| p.locations));
+        assert_eq!(None, find_shortest_path(&ring, &0, &PathTarget::Location(3), Some(Box::new(|_, next| *next != '#' && *next != '~'))));
+        assert_eq!(vec![5, 6, 7], ring.get_locations_with_value(&'~'));
+    }
+
+    #[test]
+    fn test_find_lowest_cost_path() {
+        let ring = ring();
+
+        // Swimming (cost 5 per location) is cheaper than climbing over wall (cost 20)
+        let move_cost = |_: &char, next: &char| match next {
+            '#' => Some(20),
+            '~' => Some(5),
+            _ => Some(1)
+        };
+        let (cost, path) = find_lowest_cost_path(&ring, &3, &PathTarget::Value('S'), Box::new(move_cost)).unwrap();
+        assert_eq!((3 * 5 + 4, vec![3, 4, 5, 6, 7, 8, 9, 0]), (cost, path.locations));
+
+        let (cost, path) = find_lowest_cost_path(&ring, &0, &PathTarget::Location(2), Box::new(move_cost)).unwrap();
+        assert_eq!((1 + 20, vec![0, 1, 2]), (cost, path.locations));
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let ring = ring();
+
+        let reached = flood_fill(&ring, &0, Some(Box::new(|_, next| *next != '#' && *next != '~')));
+        assert_eq!(HashSet::from([8, 9, 0, 1]), reached);
+        assert_eq!(ring.size(), flood_fill(&ring, &0, None).len());
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut ring = ring();
+
+        // Removing water leaves a gap in ring, cutting off goal from the other side
+        ring.keep_only_matching_locations(&|_, value| *value != '~');
+        assert_eq!(7, ring.size());
+        assert_eq!(None, ring.get_value(&6));
+        assert_eq!(HashSet::from([8, 9, 0, 1, 2, 3, 4]), flood_fill(&ring, &0, None));
+        assert_eq!(None, find_shortest_path(&ring, &4, &PathTarget::Location(8), Some(Box::new(|_, next| *next != '#'))));
+    }
+
+    #[test]
+    fn test_remove_location() {
+        let mut ring = ring();
+
+        // Removing goal leaves a gap in ring, then adding it back on the other side of wall
+        assert_eq!(Some('E'), ring.remove_location(&8));
+        assert_eq!(None, ring.remove_location(&8));
+        assert_eq!(None, ring.remove_location(&42));
+        assert_eq!((9, false), (ring.size(), ring.contains_location(&8)));
+        assert_eq!(None, find_shortest_path(&ring, &0, &PathTarget::Value('E'), None));
+
+        assert_eq!(None, ring.add_location(8, '.'));
+        assert_eq!(Some('.'), ring.add_location(3, 'E'));
+        let path = find_shortest_path(&ring, &0, &PathTarget::Value('E'), Some(Box::new(|_, next| *next != '#')));
+        assert_eq!(Some(vec![0, 9, 8, 7, 6, 5, 4, 3]), path.map(|p| p.locations));
+    }
+}
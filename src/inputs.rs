@@ -0,0 +1,18 @@
+//! Puzzle inputs compiled into the binary (with `embed-inputs` feature), to run solutions without the source tree
+
+#[cfg(feature = "embed-inputs")]
+include!(concat!(env!("OUT_DIR"), "/inputs.rs"));
+
+/// Inputs embedded at build time, as (year, day, input)
+#[cfg(not(feature = "embed-inputs"))]
+pub const EMBEDDED_INPUTS: &[(u16, &str, &str)] = &[];
+
+/// Whether inputs were embedded at build time
+pub fn is_enabled() -> bool {
+    cfg!(feature = "embed-inputs")
+}
+
+/// Embedded input of given year and day folder (e.g: `day01`)
+pub fn embedded_input(year: u16, day: &str) -> Option<&'static str> {
+    EMBEDDED_INPUTS.iter().find(|(y, d, _)| *y == year && *d == day).map(|(_, _, input)| *input)
+}
@@ -1,113 +1,428 @@
+use std::env;
 use std::error::Error;
 use std::fmt;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::result::Result;
 
+use log::Level;
+use params::Params;
+use snapshot::SnapshotAction;
+use template::{render_template, TemplateValues, TEMPLATE_NAMES};
+
+pub mod answer;
 pub mod circuit;
+pub mod examples;
+pub mod grid;
+pub mod inputs;
+pub mod log;
 pub mod navigation;
 pub mod navigation3d;
+pub mod params;
+pub mod parsing;
+pub mod progress;
+pub mod snapshot;
 pub mod solutions;
+pub mod template;
 
 #[derive(Debug)]
 pub enum Script {
     Generate,
-    Run
+    Run,
+    RunAll,
+    Verify,
+    Snapshots(SnapshotAction)
 }
 
 pub struct ScriptConfig {
     pub script_type: Script,
+    pub year: Option<u16>,
+    pub root: Option<String>,
     pub solution_folder: Option<String>,
-    pub solution_number: Option<String>
+    pub solution_number: Option<String>,
+    pub bench_iterations: Option<usize>,
+    pub input: Option<String>,
+    pub example: Option<String>,
+    pub params: Params,
+    pub template: Option<String>,
+    pub title: Option<String>,
+    pub expected_answers: [Option<String>; 2],
+    pub verbosity: Level
 }
 
 impl fmt::Display for ScriptConfig {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(fmt, "{:?} {:?} {:?}", self.script_type, self.solution_folder.as_deref().unwrap(), self.solution_number.as_deref().unwrap_or_default())
+        write!(
+            fmt,
+            "{:?} {:?} {:?}",
+            self.script_type,
+            self.solution_folder.as_deref().unwrap_or_default(),
+            self.solution_number.as_deref().unwrap_or_default()
+        )
     }
 }
 
 impl ScriptConfig {
-    pub fn build(mut args: impl Iterator<Item = String>) -> Result<ScriptConfig, &'static str> {
+    fn new(script_type: Script, solution_folder: Option<String>, solution_number: Option<String>) -> ScriptConfig {
+        ScriptConfig {
+            script_type,
+            year: None,
+            root: None,
+            solution_folder,
+            solution_number,
+            bench_iterations: None,
+            input: None,
+            example: None,
+            params: Params::new(),
+            template: None,
+            title: None,
+            expected_answers: [None, None],
+            verbosity: Level::Info
+        }
+    }
+
+    pub fn build(args: impl Iterator<Item = String>) -> Result<ScriptConfig, String> {
+        let mut args = args.peekable();
         args.next(); // Skipping first argument which is the program name...
 
-        // First param: either "generate" or "dayXX"
+        // Optional year param (e.g: "2023"), latest registered year by default
+        let year = args.next_if(|arg| arg.len() == 4 && arg.parse::<u16>().is_ok()).map(|arg| arg.parse::<u16>().unwrap());
+
+        // First param: either "generate", "all", "verify", "snapshots" or "dayXX"
         let param1 = match args.next() {
             Some(arg) => arg,
-            None => return Err("Missing \"(generate|dayXX)\" argument")
+            None => return Err(String::from("Missing \"(generate|all|verify|snapshots|dayXX)\" argument"))
         };
 
         // Second param: either the "dayXX" or "solutionY"
-        if param1 == "generate" {
-            let param2 = args.next();
-            return Ok(ScriptConfig { script_type: Script::Generate, solution_folder: param2, solution_number: None });
+        let mut config = if param1 == "all" {
+            ScriptConfig::new(Script::RunAll, None, None)
+        }
+        else if param1 == "generate" {
+            let param2 = args.next_if(|arg| !arg.starts_with("--"));
+            ScriptConfig::new(Script::Generate, param2, None)
+        }
+        else if param1 == "verify" {
+            let param2 = args.next_if(|arg| !arg.starts_with("--"));
+            ScriptConfig::new(Script::Verify, param2, None)
+        }
+        else if param1 == "snapshots" {
+            let action = match args.next_if(|arg| !arg.starts_with("--")).as_deref() {
+                None | Some("review") => SnapshotAction::Review,
+                Some("accept") => SnapshotAction::Accept,
+                Some("reject") => SnapshotAction::Reject,
+                Some(action) => return Err(format!("Invalid snapshots action \"{action}\" (expected review|accept|reject)"))
+            };
+            ScriptConfig::new(Script::Snapshots(action), None, None)
         }
         else {
             let param2 = match args.next() {
                 Some(arg) => arg,
-                None => return Err("Missing \"solutionY\" argument")
+                None => return Err(String::from("Missing \"solutionY\" argument"))
             };
 
-            return Ok(ScriptConfig { script_type: Script::Run, solution_folder: Some(param1), solution_number: Some(param2) });
+            ScriptConfig::new(Script::Run, Some(param1), Some(param2))
+        };
+
+        // Remaining params: options
+        config.year = year;
+        config.read_options(args)?;
+
+        let has_run_options = config.input.is_some() || config.example.is_some() || !config.params.is_empty() || config.bench_iterations.is_some();
+        if has_run_options && !matches!(config.script_type, Script::Run) {
+            return Err(String::from("Options \"--input\", \"--example\", \"--param\" and \"--bench\" are only supported when running a single solution"));
+        }
+        let has_generate_options = config.template.is_some() || config.title.is_some() || config.expected_answers.iter().any(|a| a.is_some());
+        if has_generate_options && !matches!(config.script_type, Script::Generate) {
+            return Err(String::from("Options \"--template\", \"--title\" and \"--expectedY\" are only supported when generating a solution"));
         }
+        if config.input.is_some() && config.example.is_some() {
+            return Err(String::from("Options \"--input\" and \"--example\" cannot be used together"));
+        }
+
+        Ok(config)
+    }
+
+    fn read_options(&mut self, args: impl Iterator<Item = String>) -> Result<(), String> {
+        let mut args = args.peekable();
+        while let Some(option) = args.next() {
+            match &option[..] {
+                "--year" => {
+                    self.year = match args.next().and_then(|year| year.parse::<u16>().ok()) {
+                        Some(year) => Some(year),
+                        None => return Err(String::from("Invalid \"--year YYYY\" option"))
+                    };
+                },
+                "--root" => {
+                    self.root = match args.next() {
+                        Some(path) => Some(path),
+                        None => return Err(String::from("Missing \"--root <path>\" value"))
+                    };
+                },
+                "--bench" => {
+                    self.bench_iterations = match args.next().and_then(|n| n.parse::<usize>().ok()) {
+                        Some(n) if n > 0 => Some(n),
+                        _ => return Err(String::from("Invalid \"--bench N\" option (N must be a positive number)"))
+                    };
+                },
+                "--input" => {
+                    self.input = match args.next() {
+                        Some(path) => Some(path),
+                        None => return Err(String::from("Missing \"--input (<path>|-)\" value"))
+                    };
+                },
+                "--example" => {
+                    // Optional example name (e.g: "test2" or "test2.txt"), "test" by default
+                    let name = args.next_if(|arg| !arg.starts_with("--")).unwrap_or(String::from("test"));
+                    self.example = Some(String::from(name.trim_end_matches(".txt")));
+                },
+                "--param" => match args.next() {
+                    Some(param) => self.params.set_from_str(&param)?,
+                    None => return Err(String::from("Missing \"--param key=value\" value"))
+                },
+                "--template" => {
+                    self.template = match args.next() {
+                        Some(name) if TEMPLATE_NAMES.contains(&&name[..]) => Some(name),
+                        _ => return Err(format!("Invalid \"--template\" option (expected {})", TEMPLATE_NAMES.join("|")))
+                    };
+                },
+                "--title" => {
+                    self.title = match args.next() {
+                        Some(title) => Some(title),
+                        None => return Err(String::from("Missing \"--title <text>\" value"))
+                    };
+                },
+                "--expected1" | "--expected2" => {
+                    let idx = if option == "--expected1" {
+                        0
+                    }
+                    else {
+                        1
+                    };
+                    self.expected_answers[idx] = match args.next() {
+                        Some(answer) => Some(answer),
+                        None => return Err(format!("Missing \"{option} <answer>\" value"))
+                    };
+                },
+                "--verbosity" => {
+                    self.verbosity = match args.next() {
+                        Some(level) => level.parse::<Level>()?,
+                        None => return Err(String::from("Missing \"--verbosity (quiet|info|debug|trace)\" value"))
+                    };
+                },
+                _ => return Err(format!("Unknown option \"{option}\""))
+            }
+        }
+
+        Ok(())
     }
 }
 
 /////////////////////////////////////////////////
 
-pub fn find_next_solution_dir() -> Result<Option<String>, Box<dyn Error>> {
-    let mut dir_names = fs::read_dir("./src/solutions")
-        .unwrap()
-        .filter_map(|entry| entry.ok().and_then(|e| e.path().file_name().and_then(|n| n.to_str().map(|s| String::from(s)))))
-        .filter(|dir| dir.contains("day"))
-        .collect::<Vec<String>>();
+/// Environment variable used as project root when no `--root` option was given (e.g: `AOC_ROOT=~/aoc cargo run -- all`)
+pub const ROOT_ENV_VAR: &str = "AOC_ROOT";
 
-    dir_names.sort();
-
-    return match dir_names.last() {
-        Some(name) => {
-            let next_day_number = name.replace("day", "").parse::<i32>().unwrap() + 1;
-            let next_day_folder = format!("day{:02}", next_day_number);
-            Ok(Some(next_day_folder))
-        },
-        None => Ok(None)
+/// Project root folder (holding `src/solutions.rs`, `templates` and solution files): given `--root` path, `AOC_ROOT` environment variable, or crate folder the
+/// binary was built from
+pub fn resolve_root(root: Option<&str>) -> Result<PathBuf, String> {
+    let root = match (root, env::var(ROOT_ENV_VAR)) {
+        (Some(root), _) => PathBuf::from(root),
+        (None, Ok(root)) if !root.is_empty() => PathBuf::from(root),
+        _ => PathBuf::from(env!("CARGO_MANIFEST_DIR"))
     };
+
+    if !root.is_dir() {
+        return Err(format!("Missing project root \"{}\" (use \"--root <path>\" option or {ROOT_ENV_VAR} environment variable)", root.display()));
+    }
+    if !root.join("src/solutions.rs").is_file() {
+        return Err(format!("Invalid project root \"{}\" (no \"src/solutions.rs\" file found)", root.display()));
+    }
+
+    Ok(root)
+}
+
+/// Solutions folder of given year (e.g: `<root>/src/solutions/y2022`)
+pub fn year_dir(root: &Path, year: u16) -> PathBuf {
+    root.join("src/solutions").join(format!("y{year}"))
 }
 
-pub fn is_solution_dir(dir: &str) -> bool {
-    let dir_path = format!("./src/solutions/{}", dir);
-    Path::new(&dir_path).is_dir()
+/// Path of file in day solution folder (e.g: `<root>/src/solutions/y2022/day01/input.txt`)
+pub fn solution_file(root: &Path, year: u16, dir: &str, filename: &str) -> PathBuf {
+    year_dir(root, year).join(dir).join(filename)
 }
 
-pub fn create_solution_dir(dir: &str) -> Result<(), Box<dyn Error>> {
-    let dir_path = format!("./src/solutions/{}", dir);
-    fs::create_dir_all(dir_path)?;
+pub fn find_next_solution_dir(root: &Path, year: u16) -> Result<Option<String>, Box<dyn Error>> {
+    let year_dir = year_dir(root, year);
+    if !year_dir.is_dir() {
+        return Ok(None); // New year
+    }
+
+    let entries = fs::read_dir(&year_dir).map_err(|err| format!("Failed to read \"{}\" solutions dir: {err}", year_dir.display()))?;
+    let last_day_number = entries
+        .filter_map(|entry| entry.ok().and_then(|e| e.file_name().to_str().map(String::from)))
+        .filter_map(|name| name.strip_prefix("day").and_then(|number| number.parse::<u32>().ok()))
+        .max();
+
+    Ok(last_day_number.map(|number| format!("day{:02}", number + 1)))
+}
+
+pub fn is_solution_dir(root: &Path, year: u16, dir: &str) -> bool {
+    year_dir(root, year).join(dir).is_dir()
+}
+
+pub fn create_solution_dir(root: &Path, year: u16, dir: &str) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(year_dir(root, year).join(dir))?;
     Ok(())
 }
 
-pub fn create_solution_files(dir: &str) -> Result<(), Box<dyn Error>> {
-    let template_file = format!("./templates/default.rs");
-    let target = format!("./src/solutions/{}/mod.rs", dir);
-    fs::copy(template_file, target)?;
+pub fn create_solution_files(root: &Path, year: u16, dir: &str, template: &str, values: &TemplateValues) -> Result<(), Box<dyn Error>> {
+    let template_file = root.join("templates").join(format!("{template}.rs"));
+    let target = solution_file(root, year, dir, "mod.rs");
+    fs::write(target, render_template(&read_file(template_file)?, values))?;
 
-    let input_file = format!("./src/solutions/{}/input.txt", dir);
+    let input_file = solution_file(root, year, dir, "input.txt");
     fs::File::create(input_file)?;
 
-    let test_file = format!("./src/solutions/{}/test.txt", dir);
+    let test_file = solution_file(root, year, dir, "test.txt");
     fs::File::create(test_file)?;
     Ok(())
 }
 
-/////////////////////////////////////////////////
+/// Create year solutions module and add it to `register_years!` list in `solutions.rs`, if not already (returns whether it was registered)
+pub fn register_year(root: &Path, year: u16) -> Result<bool, Box<dyn Error>> {
+    let year_file = year_dir(root, year).join("mod.rs");
+    if !year_file.is_file() {
+        fs::create_dir_all(year_dir(root, year))?;
+        fs::write(&year_file, format!("/// Solutions year\npub const YEAR: u16 = {year};\n\nregister_solutions! {{\n}}\n"))?;
+    }
+
+    update_registry(&root.join("src/solutions.rs"), "register_years", &format!("y{year}"), None)
+}
 
-pub fn read_file(filename: &str) -> Result<String, Box<dyn Error>> {
-    let data = match fs::read_to_string(filename) {
-        Ok(data) => data,
-        Err(err) => {
-            eprintln!("Failed to read file: {err}");
-            return Err(Box::new(err));
+/// Add solution module to `register_solutions!` list of year module (returns whether it was registered)
+pub fn register_solution(root: &Path, year: u16, dir: &str, parser: Option<&str>) -> Result<bool, Box<dyn Error>> {
+    update_registry(&year_dir(root, year).join("mod.rs"), "register_solutions", dir, parser)
+}
+
+fn update_registry(registry_file: &Path, registry_macro: &str, module: &str, parser: Option<&str>) -> Result<bool, Box<dyn Error>> {
+    let source = read_file(registry_file)?;
+
+    match add_registry_entry(&source, registry_macro, module, parser)? {
+        Some(updated_source) => {
+            fs::write(registry_file, updated_source)?;
+            Ok(true)
+        },
+        None => Ok(false) // Already registered
+    }
+}
+
+/// Insert module entry (e.g: `dayXX => dayXX::read_data`) in registry macro list, keeping it sorted (`None` if already registered)
+fn add_registry_entry(source: &str, registry_macro: &str, module: &str, parser: Option<&str>) -> Result<Option<String>, String> {
+    let invocation = format!("\n{registry_macro}! {{\n");
+    let start = match source.find(&invocation) {
+        Some(idx) => idx + invocation.len(),
+        None => return Err(format!("Missing \"{registry_macro}! {{ ... }}\" list"))
+    };
+    let end = match source[start..].starts_with('}') {
+        true => start, // Empty list
+        false => match source[start..].find("\n}") {
+            Some(idx) => start + idx + 1,
+            None => return Err(format!("Unterminated \"{registry_macro}! {{ ... }}\" list"))
         }
     };
 
-    Ok(data)
+    let mut entries: Vec<String> = source[start..end].lines().map(|l| String::from(l.trim().trim_end_matches(','))).filter(|l| !l.is_empty()).collect();
+    if entries.iter().any(|entry| entry.split(" =>").next() == Some(module)) {
+        return Ok(None);
+    }
+
+    entries.push(match parser {
+        Some(parser) => format!("{module} => {module}::{parser}"),
+        None => String::from(module)
+    });
+    entries.sort();
+
+    let list = entries.iter().map(|entry| format!("    {entry}")).collect::<Vec<String>>().join(",\n");
+    Ok(Some(format!("{}{}\n{}", &source[..start], list, &source[end..])))
+}
+
+/////////////////////////////////////////////////
+
+/// File content, with file name in error message (reported by caller)
+pub fn read_file(filename: impl AsRef<Path>) -> Result<String, Box<dyn Error>> {
+    match fs::read_to_string(filename.as_ref()) {
+        Ok(data) => Ok(data),
+        Err(err) => Err(format!("Failed to read file \"{}\": {err}", filename.as_ref().display()).into())
+    }
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_root() {
+        let root = resolve_root(Some(env!("CARGO_MANIFEST_DIR"))).unwrap();
+        assert!(is_solution_dir(&root, 2022, "day01"));
+        assert_eq!(Some(String::from("day26")), find_next_solution_dir(&root, 2022).unwrap());
+        assert_eq!(None, find_next_solution_dir(&root, 1999).unwrap());
+
+        assert!(resolve_root(Some("./missing/root")).is_err());
+        assert!(resolve_root(Some(&format!("{}/src", env!("CARGO_MANIFEST_DIR")))).is_err());
+    }
+
+    #[test]
+    fn test_build_run_options() {
+        let build = |args: &str| ScriptConfig::build(format!("aoc {args}").split_whitespace().map(String::from));
+
+        assert_eq!(Some(5), build("day01 solution1 --bench 5").unwrap().bench_iterations);
+        assert!(build("day01 solution1 --bench 0").is_err());
+        assert!(build("all --bench 5").is_err());
+        assert!(build("verify --bench 5").is_err());
+        assert!(build("verify day01 --example").is_err());
+    }
+
+    #[test]
+    fn test_read_file() {
+        let err = read_file("./missing/file.txt").unwrap_err();
+        assert!(err.to_string().starts_with("Failed to read file \"./missing/file.txt\": "));
+    }
+
+    #[test]
+    fn test_add_registry_entry() {
+        let source =
+            "/// Solutions year\npub const YEAR: u16 = 2022;\n\nregister_solutions! {\n    day01 => day01::get_values,\n    day03\n}\n\npub fn other() {}\n";
+
+        let updated = add_registry_entry(source, "register_solutions", "day02", Some("read_map")).unwrap().unwrap();
+        assert_eq!(
+            "/// Solutions year\npub const YEAR: u16 = 2022;\n\nregister_solutions! {\n    day01 => day01::get_values,\n    day02 => day02::read_map,\n    day03\n}\n\npub fn other() {}\n",
+            updated
+        );
+
+        let updated = add_registry_entry(&updated, "register_solutions", "day04", None).unwrap().unwrap();
+        assert!(updated.contains("    day03,\n    day04\n}"));
+
+        // Idempotent
+        assert_eq!(None, add_registry_entry(&updated, "register_solutions", "day02", None).unwrap());
+        assert_eq!(None, add_registry_entry(&updated, "register_solutions", "day04", Some("read_map")).unwrap());
+        assert!(add_registry_entry("fn main() {}", "register_solutions", "day01", None).is_err());
+    }
+
+    #[test]
+    fn test_add_registry_entry_to_empty_list() {
+        let source = "/// Solutions year\npub const YEAR: u16 = 2023;\n\nregister_solutions! {\n}\n";
+
+        let updated = add_registry_entry(source, "register_solutions", "day01", None).unwrap().unwrap();
+        assert_eq!("/// Solutions year\npub const YEAR: u16 = 2023;\n\nregister_solutions! {\n    day01\n}\n", updated);
+
+        let source = "register_years! {\n    y2022\n}\n";
+        assert_eq!(None, add_registry_entry(&format!("\n{source}"), "register_years", "y2022", None).unwrap());
+        assert_eq!(
+            "\nregister_years! {\n    y2022,\n    y2023\n}\n",
+            add_registry_entry(&format!("\n{source}"), "register_years", "y2023", None).unwrap().unwrap()
+        );
+    }
 }
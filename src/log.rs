@@ -0,0 +1,102 @@
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Logging verbosity level
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Level {
+    Quiet = 0,
+    Info = 1,
+    Debug = 2,
+    Trace = 3
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Level, String> {
+        match s {
+            "quiet" => Ok(Level::Quiet),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!("Invalid verbosity \"{s}\" (expected quiet|info|debug|trace)"))
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let name = match self {
+            Level::Quiet => "quiet",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace"
+        };
+
+        write!(fmt, "{name}")
+    }
+}
+
+/// Environment variable used as default verbosity when none was set (e.g: `AOC_VERBOSITY=debug cargo test`)
+pub const VERBOSITY_ENV_VAR: &str = "AOC_VERBOSITY";
+
+const UNSET_LEVEL: u8 = u8::MAX;
+
+static LEVEL: AtomicU8 = AtomicU8::new(UNSET_LEVEL);
+
+/// Set current verbosity level
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Get current verbosity level (quiet by default, unless overridden by environment variable)
+pub fn level() -> Level {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => Level::Quiet,
+        1 => Level::Info,
+        2 => Level::Debug,
+        3 => Level::Trace,
+        _ => {
+            let level = env::var(VERBOSITY_ENV_VAR).ok().and_then(|l| l.parse::<Level>().ok()).unwrap_or(Level::Quiet);
+            set_level(level);
+            level
+        }
+    }
+}
+
+/// Whether messages at given level are currently shown
+pub fn enabled(level: Level) -> bool {
+    level != Level::Quiet && level <= self::level()
+}
+
+/// Print message at info level
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Info) {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Print message at debug level
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Print message at trace level
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            println!($($arg)*);
+        }
+    };
+}
@@ -1,61 +1,165 @@
 use std::env;
 use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::panic;
+use std::path::{Path, PathBuf};
 use std::process;
+use std::time::{Duration, Instant};
 
-use aoc2022::solutions;
-use aoc2022::{create_solution_dir, create_solution_files, find_next_solution_dir, is_solution_dir, read_file, Script, ScriptConfig};
+use aoc::answer::{read_expected_answers, Answer, SolveError, ANSWERS_FILE};
+use aoc::inputs;
+use aoc::log::{self, Level};
+use aoc::params::Params;
+use aoc::progress;
+use aoc::snapshot::{accepted_file, diff_lines, find_pending_snapshots, SnapshotAction};
+use aoc::solutions::{self, Solution};
+use aoc::template::{template_parser, TemplateValues};
+use aoc::{
+    create_solution_dir, create_solution_files, find_next_solution_dir, is_solution_dir, read_file, register_solution, register_year, resolve_root,
+    solution_file, Script, ScriptConfig
+};
+
+/// Process exit codes, so that scripts can tell failures apart
+const EXIT_FAILURE: i32 = 1;
+const EXIT_MISSING_INPUT: i32 = 2;
+const EXIT_PARSE_ERROR: i32 = 3;
+const EXIT_NO_SOLUTION: i32 = 4;
+
+/// Missing solution input file
+#[derive(Debug)]
+struct MissingInputError(String);
+
+impl fmt::Display for MissingInputError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(fmt, "Missing input file \"{}\"", self.0)
+    }
+}
+
+impl Error for MissingInputError {}
 
 fn main() {
     // Parsing arguments
     let config = ScriptConfig::build(env::args()).unwrap_or_else(|err| {
         eprintln!("Problem parsing arguments! {err}\n");
-        eprintln!("USAGE: cargo run -- (generate|dayXX) [solutionY]");
+        eprintln!(
+            "USAGE: cargo run -- [YYYY] dayXX solutionY [--input <path>|-] [--example [name]] [--param key=value] [--bench N] [--verbosity quiet|info|debug|trace] [--root <path>]"
+        );
+        eprintln!(
+            "       cargo run -- [YYYY] generate [dayXX] [--template default|circuit|grid|grid3d|parser|search] [--title <text>] [--expected1 <answer>] [--expected2 <answer>] [--root <path>]"
+        );
+        eprintln!("       cargo run -- [YYYY] (all|verify [dayXX]) [--verbosity quiet|info|debug|trace] [--root <path>]");
+        eprintln!("       cargo run -- snapshots [review|accept|reject] [--root <path>]");
+        eprintln!("       (or \"--year YYYY\" option, latest registered year by default, or every year for \"all\" and \"verify\")");
         process::exit(1);
     });
 
+    log::set_level(config.verbosity);
+
+    // Progress of long running solutions is only shown when running a single one in a terminal
+    progress::set_enabled(matches!(config.script_type, Script::Run) && log::enabled(Level::Info) && io::stderr().is_terminal());
+
+    // Locating project files (solutions, inputs and templates)
+    let root = match resolve_root(config.root.as_deref()) {
+        Ok(root) => root,
+        Err(err) if inputs::is_enabled() && !matches!(config.script_type, Script::Generate) => {
+            // Running solutions with their embedded inputs, without source tree
+            eprintln!("Warning: {err}, using embedded inputs");
+            PathBuf::from(config.root.as_deref().unwrap_or("."))
+        },
+        Err(err) => {
+            eprintln!("Error: {err}");
+            process::exit(EXIT_FAILURE);
+        }
+    };
+
     // Running scripts
     let result = match config.script_type {
-        Script::Generate => generate_next_day_solution(config),
-        Script::Run => run_day_solution(config)
+        Script::Generate => generate_next_day_solution(&root, config),
+        Script::Run => run_day_solution(&root, config),
+        Script::RunAll => run_all_solutions(&root, config),
+        Script::Verify => verify_solutions(&root, config),
+        Script::Snapshots(action) => review_snapshots(&root, action)
     };
 
     match result {
         Ok(()) => println!("Done !"),
-        Err(err) => eprintln!("Error: {err}")
+        Err(err) => {
+            eprintln!("Error: {err}");
+            process::exit(exit_code(err.as_ref()));
+        }
+    }
+}
+
+fn exit_code(err: &(dyn Error + 'static)) -> i32 {
+    if err.is::<MissingInputError>() {
+        return EXIT_MISSING_INPUT;
+    }
+
+    match err.downcast_ref::<SolveError>() {
+        Some(SolveError::Parse(_)) => EXIT_PARSE_ERROR,
+        Some(SolveError::NoSolution(_)) => EXIT_NO_SOLUTION,
+        Some(SolveError::InvalidParam(_)) | None => EXIT_FAILURE
     }
 }
 
 /////////////////////////////////////////////////
 
-fn generate_next_day_solution(config: ScriptConfig) -> Result<(), Box<dyn Error>> {
+fn generate_next_day_solution(root: &Path, config: ScriptConfig) -> Result<(), Box<dyn Error>> {
+    let year = config.year.unwrap_or(solutions::latest_year());
     let folder = match config.solution_folder {
         Some(folder) => folder,
-        None => match find_next_solution_dir() {
-            Ok(result) => result.unwrap_or(String::from("day01")),
-            Err(_) => String::from("day01")
-        }
+        None => find_next_solution_dir(root, year)?.unwrap_or(String::from("day01"))
     };
 
     if folder == "day26" {
-        return Ok(()); // No more solution
-    }
-
-    if is_solution_dir(&folder) {
-        return Ok(()); // Solution folder already exists
+        return Ok(()); // No more solution this year
     }
 
-    match create_solution_dir(&folder) {
-        Ok(()) => println!("Created \"{folder}\" solution dir"),
+    // Registering year module (if not already), so that its solutions get compiled
+    match register_year(root, year) {
+        Ok(true) => println!("Registered \"y{year}\" in solutions.rs"),
+        Ok(false) => (),
         Err(err) => {
-            eprintln!("Failed to create solution dir: {err}");
+            eprintln!("Failed to register year: {err}");
             return Err(err);
         }
     }
 
-    match create_solution_files(&folder) {
-        Ok(()) => println!("Created \"{folder}\" solution files"),
+    let template = config.template.as_deref().unwrap_or("default");
+    if !is_solution_dir(root, year, &folder) {
+        match create_solution_dir(root, year, &folder) {
+            Ok(()) => println!("Created \"{folder}\" solution dir"),
+            Err(err) => {
+                eprintln!("Failed to create solution dir: {err}");
+                return Err(err);
+            }
+        }
+
+        let values = TemplateValues {
+            day: &folder,
+            title: config.title.as_deref(),
+            expected_answers: [config.expected_answers[0].as_deref(), config.expected_answers[1].as_deref()]
+        };
+        match create_solution_files(root, year, &folder, template, &values) {
+            Ok(()) => println!("Created \"{folder}\" solution files (from \"{template}\" template)"),
+            Err(err) => {
+                eprintln!("Failed to create solution files: {err}");
+                return Err(err);
+            }
+        }
+    }
+    else if !solution_file(root, year, &folder, "mod.rs").is_file() {
+        return Ok(()); // Solution folder already exists, but without module to register
+    }
+
+    // Registering solution module (if not already), so that it compiles and runs right away
+    match register_solution(root, year, &folder, template_parser(template)) {
+        Ok(true) => println!("Registered \"{folder}\" in y{year}/mod.rs"),
+        Ok(false) => (),
         Err(err) => {
-            eprintln!("Failed to create solution files: {err}");
+            eprintln!("Failed to register solution: {err}");
             return Err(err);
         }
     }
@@ -63,174 +167,380 @@ fn generate_next_day_solution(config: ScriptConfig) -> Result<(), Box<dyn Error>
     Ok(())
 }
 
-fn run_day_solution(config: ScriptConfig) -> Result<(), Box<dyn Error>> {
+fn run_day_solution(root: &Path, config: ScriptConfig) -> Result<(), Box<dyn Error>> {
     let folder = config.solution_folder.unwrap();
-    let solution_key = format!("{}-{}", folder, config.solution_number.unwrap());
+    let solution_number = config.solution_number.unwrap();
+    let year = config.year.unwrap_or(solutions::latest_year());
 
-    if !is_solution_dir(&folder) {
-        // Solution folder does not exists yet
-        let error: Result<Result<(), Box<dyn Error>>, &str> = Err("Non existing solution \"{folder}\"");
-        return error?;
-    }
+    let solution = match solutions::find_solution(year, &folder, &solution_number) {
+        Some(solution) => solution,
+        None if !is_solution_dir(root, year, &folder) => {
+            // Solution folder does not exists yet
+            return Err(format!("Non existing solution \"{folder}\" for {year}").into());
+        },
+        None => return Err(format!("Unknown solution \"{solution_number}\" for \"{folder}\" (is \"{folder}\" registered in y{year}/mod.rs ?)").into())
+    };
 
-    // Reading input file
-    let input_file = format!("./src/solutions/{}/input.txt", folder);
-    let data = read_file(&input_file)?;
+    // Reading input file (or example file, or standard input)
+    let data = match (config.input.as_deref(), config.example.as_deref()) {
+        (_, Some(example)) => read_input_file(&solution_file(root, year, &folder, &format!("{example}.txt")))?,
+        (Some("-"), None) => {
+            let mut data = String::new();
+            io::stdin().read_to_string(&mut data)?;
+            data
+        },
+        (Some(input_file), None) => read_input_file(input_file)?,
+        (None, None) => match read_day_input(root, solution)? {
+            Some(data) => data,
+            None => return Err(MissingInputError(solution_file(root, year, &folder, "input.txt").display().to_string()).into())
+        }
+    };
 
     // Running solution
-    match &solution_key[..] {
-        "day01-1" => {
-            solutions::day01::solution1(data);
-        },
-        "day01-2" => {
-            solutions::day01::solution2(data);
-        },
-        "day02-1" => {
-            solutions::day02::solution1(data);
-        },
-        "day02-2" => {
-            solutions::day02::solution2(data);
-        },
-        "day03-1" => {
-            solutions::day03::solution1(data);
-        },
-        "day03-2" => {
-            solutions::day03::solution2(data);
-        },
-        "day04-1" => {
-            solutions::day04::solution1(data);
-        },
-        "day04-2" => {
-            solutions::day04::solution2(data);
-        },
-        "day05-1" => {
-            solutions::day05::solution1(data);
-        },
-        "day05-2" => {
-            solutions::day05::solution2(data);
-        },
-        "day06-1" => {
-            solutions::day06::solution1(data);
-        },
-        "day06-2" => {
-            solutions::day06::solution2(data);
-        },
-        "day07-1" => {
-            solutions::day07::solution1(data);
-        },
-        "day07-2" => {
-            solutions::day07::solution2(data);
-        },
-        "day08-1" => {
-            solutions::day08::solution1(data);
-        },
-        "day08-2" => {
-            solutions::day08::solution2(data);
-        },
-        "day09-1" => {
-            solutions::day09::solution1(data);
-        },
-        "day09-2" => {
-            solutions::day09::solution2(data);
-        },
-        "day10-1" => {
-            solutions::day10::solution1(data);
-        },
-        "day10-2" => {
-            solutions::day10::solution2(data);
-        },
-        "day11-1" => {
-            solutions::day11::solution1(data);
-        },
-        "day11-2" => {
-            solutions::day11::solution2(data);
-        },
-        "day12-1" => {
-            solutions::day12::solution1(data);
-        },
-        "day12-2" => {
-            solutions::day12::solution2(data);
-        },
-        "day13-1" => {
-            solutions::day13::solution1(data);
-        },
-        "day13-2" => {
-            solutions::day13::solution2(data);
-        },
-        "day14-1" => {
-            solutions::day14::solution1(data);
-        },
-        "day14-2" => {
-            solutions::day14::solution2(data);
-        },
-        "day15-1" => {
-            solutions::day15::solution1(data);
-        },
-        "day15-2" => {
-            solutions::day15::solution2(data);
-        },
-        "day16-1" => {
-            solutions::day16::solution1(data);
-        },
-        "day16-2" => {
-            solutions::day16::solution2(data);
-        },
-        "day17-1" => {
-            solutions::day17::solution1(data);
-        },
-        "day17-2" => {
-            solutions::day17::solution2(data);
-        },
-        "day18-1" => {
-            solutions::day18::solution1(data);
-        },
-        "day18-2" => {
-            solutions::day18::solution2(data);
-        },
-        "day19-1" => {
-            solutions::day19::solution1(data);
-        },
-        "day19-2" => {
-            solutions::day19::solution2(data);
-        },
-        "day20-1" => {
-            solutions::day20::solution1(data);
-        },
-        "day20-2" => {
-            solutions::day20::solution2(data);
-        },
-        "day21-1" => {
-            solutions::day21::solution1(data);
-        },
-        "day21-2" => {
-            solutions::day21::solution2(data);
-        },
-        "day22-1" => {
-            solutions::day22::solution1(data);
-        },
-        "day22-2" => {
-            solutions::day22::solution2(data);
-        },
-        "day23-1" => {
-            solutions::day23::solution1(data);
-        },
-        "day23-2" => {
-            solutions::day23::solution2(data);
-        },
-        "day24-1" => {
-            solutions::day24::solution1(data);
-        },
-        "day24-2" => {
-            solutions::day24::solution2(data);
-        },
-        "day25-1" => {
-            solutions::day25::solution1(data);
-        },
-        "day25-2" => {
-            solutions::day25::solution2(data);
-        },
-        _ => ()
+    match config.bench_iterations {
+        Some(nb_iterations) => bench_solution(solution, data, &config.params, nb_iterations)?,
+        None => {
+            let answer = (solution.run)(&data, &config.params)?;
+            print_answer(solution, &answer);
+        }
+    }
+
+    for key in config.params.unused_keys() {
+        eprintln!("Warning: unused parameter \"{key}\" for {} solution{}", solution.day, solution.part);
+    }
+
+    Ok(())
+}
+
+fn read_input_file(input_file: impl AsRef<Path>) -> Result<String, Box<dyn Error>> {
+    if !input_file.as_ref().is_file() {
+        return Err(MissingInputError(input_file.as_ref().display().to_string()).into());
+    }
+
+    read_file(input_file)
+}
+
+/// Solution day `input.txt` file, or input embedded at build time when file is missing (with `embed-inputs` feature)
+fn read_day_input(root: &Path, solution: &Solution) -> Result<Option<String>, Box<dyn Error>> {
+    let input_file = solution_file(root, solution.year, solution.day, "input.txt");
+    if input_file.is_file() {
+        return Ok(Some(read_file(input_file)?));
+    }
+
+    Ok(inputs::embedded_input(solution.year, solution.day).map(String::from))
+}
+
+fn bench_solution(solution: &Solution, data: String, params: &Params, nb_iterations: usize) -> Result<(), Box<dyn Error>> {
+    let mut answer = Answer::Empty;
+    let mut parse_durations: Vec<Duration> = vec![];
+    let mut solve_durations: Vec<Duration> = vec![];
+    let mut total_durations: Vec<Duration> = vec![];
+
+    for _ in 0..nb_iterations {
+        match solution.timed_run {
+            Some(timed_run) => {
+                let timed_answer = timed_run(&data, params)?;
+                parse_durations.push(timed_answer.parse_duration);
+                solve_durations.push(timed_answer.solve_duration);
+                total_durations.push(timed_answer.parse_duration + timed_answer.solve_duration);
+                answer = timed_answer.answer;
+            },
+            None => {
+                let start = Instant::now();
+                answer = (solution.run)(&data, params)?;
+                total_durations.push(start.elapsed());
+            }
+        }
+    }
+
+    print_answer(solution, &answer);
+
+    println!("Benchmark: {} solution{} ({nb_iterations} iterations)", solution.day, solution.part);
+    println!("| {:<5} | {:>12} | {:>12} | {:>12} |", "Step", "Min", "Median", "Max");
+    println!("|{}|{}|{}|{}|", "-".repeat(7), "-".repeat(14), "-".repeat(14), "-".repeat(14));
+    if solution.timed_run.is_some() {
+        print_bench_row("parse", parse_durations);
+        print_bench_row("solve", solve_durations);
+    }
+    print_bench_row("total", total_durations);
+    println!("=========================");
+
+    Ok(())
+}
+
+fn print_bench_row(step: &str, mut durations: Vec<Duration>) {
+    durations.sort();
+
+    let min = durations[0];
+    let median = durations[durations.len() / 2];
+    let max = durations[durations.len() - 1];
+
+    println!("| {:<5} | {:>12} | {:>12} | {:>12} |", step, format_duration(&min), format_duration(&median), format_duration(&max));
+}
+
+fn format_duration(duration: &Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn print_answer(solution: &Solution, answer: &Answer) {
+    let answer = answer.to_string();
+
+    println!("=========================");
+    if answer.contains('\n') {
+        println!("Solution{}:\n{answer}", solution.part);
+    }
+    else {
+        println!("Solution{}: {answer}", solution.part);
+    }
+    println!("=========================");
+}
+
+/// Status of a solution run
+enum RunStatus {
+    Ok(Answer),
+    Failed(SolveError),
+    Panicked,
+    MissingInput
+}
+
+fn run_all_solutions(root: &Path, config: ScriptConfig) -> Result<(), Box<dyn Error>> {
+    let mut results: Vec<(&Solution, RunStatus, Option<Duration>)> = vec![];
+
+    // Silencing panic messages while running, failures are reported in summary table instead
+    let silent_panics = SilentPanics::new();
+
+    for solution in solutions::solutions(config.year) {
+        let (status, duration) = run_solution_safely(root, solution)?;
+        results.push((solution, status, duration));
     }
 
+    drop(silent_panics);
+
+    print_summary_table(&results);
+
     Ok(())
 }
+
+/// Silences panic messages until dropped, restoring the previous panic hook (even when returning early on error)
+struct SilentPanics {
+    previous_hook: Option<Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send + 'static>>
+}
+
+impl SilentPanics {
+    fn new() -> SilentPanics {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        SilentPanics { previous_hook: Some(previous_hook) }
+    }
+}
+
+impl Drop for SilentPanics {
+    fn drop(&mut self) {
+        if let Some(previous_hook) = self.previous_hook.take() {
+            panic::set_hook(previous_hook);
+        }
+    }
+}
+
+/// Run solution against its input file, catching any panic
+fn run_solution_safely(root: &Path, solution: &Solution) -> Result<(RunStatus, Option<Duration>), Box<dyn Error>> {
+    let data = match read_day_input(root, solution)? {
+        Some(data) => data,
+        None => return Ok((RunStatus::MissingInput, None))
+    };
+
+    let start = Instant::now();
+    let result = panic::catch_unwind(|| (solution.run)(&data, &Params::new()));
+    let duration = start.elapsed();
+
+    match result {
+        Ok(Ok(answer)) => Ok((RunStatus::Ok(answer), Some(duration))),
+        Ok(Err(error)) => Ok((RunStatus::Failed(error), Some(duration))),
+        Err(_) => Ok((RunStatus::Panicked, Some(duration)))
+    }
+}
+
+fn print_summary_table(results: &[(&Solution, RunStatus, Option<Duration>)]) {
+    let separator = format!("|{}|{}|{}|{}|{}|{}|", "-".repeat(6), "-".repeat(7), "-".repeat(6), "-".repeat(22), "-".repeat(14), "-".repeat(15));
+
+    println!("=========================");
+    println!("| {:<4} | {:<5} | {:<4} | {:<20} | {:>12} | {:<13} |", "Year", "Day", "Part", "Answer", "Time", "Status");
+    println!("{separator}");
+    for (solution, status, duration) in results {
+        let (answer, status) = match status {
+            RunStatus::Ok(answer) => (format_table_answer(answer), "ok"),
+            RunStatus::Failed(error) => (String::new(), failure_status(error)),
+            RunStatus::Panicked => (String::new(), "panicked"),
+            RunStatus::MissingInput => (String::new(), "missing input")
+        };
+        let time = match duration {
+            Some(d) => format_duration(d),
+            None => String::new()
+        };
+
+        println!("| {:<4} | {:<5} | {:>4} | {:<20} | {:>12} | {:<13} |", solution.year, solution.day, solution.part, answer, time, status);
+    }
+    println!("=========================");
+
+    print_solve_errors(results.iter().filter_map(|(solution, status, _)| match status {
+        RunStatus::Failed(error) => Some((*solution, error)),
+        _ => None
+    }));
+}
+
+fn failure_status(error: &SolveError) -> &'static str {
+    match error {
+        SolveError::Parse(_) => "parse error",
+        SolveError::NoSolution(_) => "no solution",
+        SolveError::InvalidParam(_) => "invalid param"
+    }
+}
+
+/// Print failure details of solutions (e.g: input parsing diagnostics)
+fn print_solve_errors<'a>(errors: impl Iterator<Item = (&'a Solution, &'a SolveError)>) {
+    for (solution, error) in errors {
+        println!("{} {} solution{}: {error}", solution.year, solution.day, solution.part);
+        println!("=========================");
+    }
+}
+
+fn format_table_answer(answer: &Answer) -> String {
+    let answer = answer.to_string();
+
+    if answer.contains('\n') {
+        return String::from("(multiline)");
+    }
+
+    if answer.chars().count() > 20 {
+        return format!("{}...", answer.chars().take(17).collect::<String>());
+    }
+
+    answer
+}
+
+fn verify_solutions(root: &Path, config: ScriptConfig) -> Result<(), Box<dyn Error>> {
+    // Single day is verified in given (or latest) year, every registered day otherwise
+    let year = match &config.solution_folder {
+        Some(folder) => {
+            let year = config.year.unwrap_or(solutions::latest_year());
+            let is_registered = solutions::solutions(Some(year)).any(|s| &s.day == folder);
+            if !is_registered && !is_solution_dir(root, year, folder) {
+                return Err(format!("Non existing solution \"{folder}\" for {year}").into());
+            }
+            Some(year)
+        },
+        None => config.year
+    };
+
+    let mut rows: Vec<(&Solution, String, String, &str)> = vec![];
+    let mut solve_errors: Vec<(&Solution, SolveError)> = vec![];
+
+    // Silencing panic messages while running, failures are reported in verification table instead
+    let silent_panics = SilentPanics::new();
+
+    for solution in solutions::solutions(year).filter(|s| config.solution_folder.as_ref().map_or(true, |folder| folder == s.day)) {
+        let expected_answers = read_expected_answers(&solution_file(root, solution.year, solution.day, ANSWERS_FILE))?;
+        let expected = match expected_answers.as_ref().and_then(|answers| answers.get(&format!("solution{}", solution.part))) {
+            Some(answer) => answer.clone(),
+            None => {
+                rows.push((solution, String::new(), String::new(), "missing answer"));
+                continue;
+            }
+        };
+
+        let (answer, status) = match run_solution_safely(root, solution)?.0 {
+            RunStatus::Ok(answer) if answer == expected => (format_table_answer(&answer), "ok"),
+            RunStatus::Ok(answer) => (format_table_answer(&answer), "mismatch"),
+            RunStatus::Failed(error) => {
+                let status = failure_status(&error);
+                solve_errors.push((solution, error));
+                (String::new(), status)
+            },
+            RunStatus::Panicked => (String::new(), "panicked"),
+            RunStatus::MissingInput => (String::new(), "missing input")
+        };
+        rows.push((solution, format_table_answer(&expected), answer, status));
+    }
+
+    drop(silent_panics);
+
+    let separator = format!("|{}|{}|{}|{}|{}|{}|", "-".repeat(6), "-".repeat(7), "-".repeat(6), "-".repeat(22), "-".repeat(22), "-".repeat(16));
+
+    println!("=========================");
+    println!("| {:<4} | {:<5} | {:<4} | {:<20} | {:<20} | {:<14} |", "Year", "Day", "Part", "Expected", "Answer", "Status");
+    println!("{separator}");
+    for (solution, expected, answer, status) in rows.iter() {
+        println!("| {:<4} | {:<5} | {:>4} | {:<20} | {:<20} | {:<14} |", solution.year, solution.day, solution.part, expected, answer, status);
+    }
+    println!("=========================");
+
+    print_solve_errors(solve_errors.iter().map(|(solution, error)| (*solution, error)));
+
+    // Solutions without answer or input are not verified, which is a failure as well (nothing would be checked otherwise)
+    let nb_verified = rows.iter().filter(|(_, _, _, status)| *status == "ok").count();
+    let nb_failures = rows.len() - nb_verified;
+    if nb_failures > 0 || nb_verified == 0 {
+        return Err(format!("{nb_failures} solution(s) not matching expected answer or not verified ({nb_verified} verified)").into());
+    }
+
+    Ok(())
+}
+
+/// Review pending snapshots written by failing snapshot tests (showing differences), then accept or reject them
+fn review_snapshots(root: &Path, action: SnapshotAction) -> Result<(), Box<dyn Error>> {
+    let pending_files = find_pending_snapshots(&root.join("src"))?;
+    if pending_files.is_empty() {
+        println!("No pending snapshot");
+        return Ok(());
+    }
+
+    for pending_file in pending_files {
+        let snapshot_file = accepted_file(&pending_file);
+        match action {
+            SnapshotAction::Review => {
+                let actual = read_file(&pending_file)?;
+                match snapshot_file.is_file() {
+                    true => println!("Changed snapshot \"{}\":\n{}", snapshot_file.display(), diff_lines(&read_file(&snapshot_file)?, &actual)),
+                    false => println!("New snapshot \"{}\":\n{}", snapshot_file.display(), diff_lines("", &actual))
+                }
+                println!("=========================");
+            },
+            SnapshotAction::Accept => {
+                fs::rename(&pending_file, &snapshot_file)?;
+                println!("Accepted snapshot \"{}\"", snapshot_file.display());
+            },
+            SnapshotAction::Reject => {
+                fs::remove_file(&pending_file)?;
+                println!("Rejected snapshot \"{}\"", snapshot_file.display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Project root with day01 input and recorded answers only
+    fn fixture_root() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/verify")
+    }
+
+    fn verify(args: &str) -> Result<(), Box<dyn Error>> {
+        let config = ScriptConfig::build(format!("aoc 2022 verify {args}").split_whitespace().map(String::from)).unwrap();
+        verify_solutions(&fixture_root(), config)
+    }
+
+    #[test]
+    fn test_verify_solutions() {
+        assert!(verify("day01").is_ok());
+
+        // Missing answers and inputs are failures
+        assert!(verify("day02").is_err());
+        assert!(verify("").is_err());
+    }
+}
@@ -1,6 +1,12 @@
+use crate::debug;
+pub use crate::grid::find_shortest_path;
+use crate::grid::{self, BoundedMap, Bounds, GridLike};
+use crate::log::{self, Level};
+use crate::parsing::ParseError;
 use num::Signed;
+use std::cell::Cell;
 use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
+use std::collections::hash_map;
 use std::hash::{Hash, Hasher};
 use std::ops::{Add, Sub};
 
@@ -96,6 +102,114 @@ where
     }
 }
 
+/// A 2d rectangle, both corners included
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Rect<T> {
+    pub min: Coordinate<T>,
+    pub max: Coordinate<T>
+}
+
+impl<T> Rect<T>
+where
+    T: Ord + Copy
+{
+    /// Rectangle of a single location
+    pub fn new(location: Coordinate<T>) -> Rect<T> {
+        Rect { min: location, max: location }
+    }
+
+    /// Smallest rectangle containing every location (`None` without locations)
+    pub fn from_locations<'a>(mut locations: impl Iterator<Item = &'a Coordinate<T>>) -> Option<Rect<T>>
+    where
+        T: 'a
+    {
+        let first_location = locations.next()?;
+        Some(locations.fold(Rect::new(*first_location), |rect, location| rect.extend(location)))
+    }
+
+    /// Smallest rectangle containing both rectangle and location
+    pub fn extend(&self, location: &Coordinate<T>) -> Rect<T> {
+        Rect {
+            min: Coordinate { x: self.min.x.min(location.x), y: self.min.y.min(location.y) },
+            max: Coordinate { x: self.max.x.max(location.x), y: self.max.y.max(location.y) }
+        }
+    }
+
+    pub fn contains(&self, location: &Coordinate<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&location.x) && (self.min.y..=self.max.y).contains(&location.y)
+    }
+
+    /// Whether location is on one of the rectangle sides (e.g: removing it may shrink bounds)
+    pub fn is_on_border(&self, location: &Coordinate<T>) -> bool {
+        location.x == self.min.x || location.x == self.max.x || location.y == self.min.y || location.y == self.max.y
+    }
+}
+
+impl<T> Rect<T>
+where
+    T: Copy + From<isize> + Into<isize>
+{
+    pub fn width(&self) -> T {
+        T::from(self.max.x.into() - self.min.x.into() + 1) // Both bounds included
+    }
+
+    pub fn height(&self) -> T {
+        T::from(self.max.y.into() - self.min.y.into() + 1) // Both bounds included
+    }
+}
+
+impl<T> Bounds<Coordinate<T>> for Rect<T>
+where
+    T: Ord + Copy
+{
+    fn new(location: Coordinate<T>) -> Rect<T> {
+        Rect::new(location)
+    }
+
+    fn extend(&self, location: &Coordinate<T>) -> Rect<T> {
+        Rect::extend(self, location)
+    }
+
+    fn is_on_border(&self, location: &Coordinate<T>) -> bool {
+        Rect::is_on_border(self, location)
+    }
+}
+
+/// Text layout of grids, for `parse_with` and `render_with`
+#[derive(Debug, Copy, Clone)]
+pub struct TextOptions<T> {
+    /// Location of first character of text (`(0, 0)` when parsing and grid top left location when rendering, by default)
+    pub origin: Option<Coordinate<T>>,
+    /// Character of locations missing from grid when rendering
+    pub blank: char,
+    /// Whether blank characters are skipped when parsing (without being mapped), and trimmed from end of lines when rendering
+    pub skip_blanks: bool
+}
+
+impl<T> Default for TextOptions<T> {
+    fn default() -> TextOptions<T> {
+        TextOptions { origin: None, blank: ' ', skip_blanks: false }
+    }
+}
+
+/// Call closure with location of every character of text
+fn for_each_text_location<T>(text: &str, options: &TextOptions<T>, mut f: impl FnMut(Coordinate<T>, char))
+where
+    T: Copy + From<isize> + Into<isize>
+{
+    let (origin_x, origin_y): (isize, isize) = options.origin.map(|o| (o.x.into(), o.y.into())).unwrap_or((0, 0));
+
+    for (y, line) in text.lines().enumerate() {
+        for (x, char) in line.chars().enumerate() {
+            if options.skip_blanks && char == options.blank {
+                continue;
+            }
+
+            f(Coordinate { x: T::from(origin_x + x as isize), y: T::from(origin_y + y as isize) }, char);
+        }
+    }
+}
+
 /// A 2d direction
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
 pub enum Direction {
@@ -199,12 +313,12 @@ where
         }
     }
     else {
-        // Vertical line
+        // Vertical line (y axis pointing down)
         if start.y < end.y {
-            Direction::Up
+            Direction::Down
         }
         else {
-            Direction::Down
+            Direction::Up
         }
     }
 }
@@ -234,9 +348,115 @@ pub fn get_direction_after_rotation(direction: &Direction, rotation: &Rotation)
     }
 }
 
-/// A 2d grid implemented as an hash map of locations
+/// A 2d map of values by location, shared by sparse `Grid` and dense `DenseGrid` (so that solutions can switch from one to the other)
+pub trait GridMap<T>: GridLike<Location = Coordinate<T>>
+where
+    T: Ord + Copy + Hash + From<isize> + Into<isize>
+{
+    /// Smallest rectangle containing every location (`None` for empty grid)
+    fn bounds(&self) -> Option<Rect<T>>;
+
+    /// Panics on empty grid (see `bounds`)
+    fn min_x(&self) -> T {
+        self.bounds().expect(EMPTY_GRID_BOUNDS).min.x
+    }
+
+    /// Panics on empty grid (see `bounds`)
+    fn min_y(&self) -> T {
+        self.bounds().expect(EMPTY_GRID_BOUNDS).min.y
+    }
+
+    /// Panics on empty grid (see `bounds`)
+    fn max_x(&self) -> T {
+        self.bounds().expect(EMPTY_GRID_BOUNDS).max.x
+    }
+
+    /// Panics on empty grid (see `bounds`)
+    fn max_y(&self) -> T {
+        self.bounds().expect(EMPTY_GRID_BOUNDS).max.y
+    }
+
+    /// Zero for empty grid
+    fn width(&self) -> T {
+        self.bounds().map(|b| b.width()).unwrap_or(T::from(0))
+    }
+
+    /// Zero for empty grid
+    fn height(&self) -> T {
+        self.bounds().map(|b| b.height()).unwrap_or(T::from(0))
+    }
+
+    fn print(&self, empty_value: &Self::Value, map_value_to_char: &dyn Fn(&Self::Value) -> &str) {
+        if !log::enabled(Level::Debug) {
+            return;
+        }
+
+        debug!("{}", self.draw(empty_value, map_value_to_char));
+    }
+
+    /// Framed drawing of grid, one row per line (e.g: for `print` or snapshot tests)
+    fn draw(&self, empty_value: &Self::Value, map_value_to_char: &dyn Fn(&Self::Value) -> &str) -> String {
+        let bounds = self.bounds();
+        let width = bounds.map(|b| b.width().into()).unwrap_or(0);
+        let separator = (0..width + 2).map(|_| "-").collect::<String>();
+
+        // Empty grid is drawn as an empty frame
+        let (min_x, max_x): (isize, isize) = bounds.map(|b| (b.min.x.into(), b.max.x.into())).unwrap_or((0, -1));
+        let (min_y, max_y): (isize, isize) = bounds.map(|b| (b.min.y.into(), b.max.y.into())).unwrap_or((0, -1));
+
+        let mut lines = vec![separator.clone()];
+        for y in min_y..max_y + 1 {
+            let mut row = String::from("");
+            for x in min_x..max_x + 1 {
+                row.push_str(map_value_to_char(self.get_value(&Coordinate { x: T::from(x), y: T::from(y) }).unwrap_or(empty_value)));
+            }
+            lines.push(format!("|{}|", row));
+        }
+        lines.push(separator);
+
+        lines.join("\n")
+    }
+
+    /// Text drawing of grid, one row per line and one character per location (inverse of `parse`)
+    fn render(&self, map_value_to_char: impl Fn(&Self::Value) -> char) -> String {
+        self.render_with(&TextOptions::default(), map_value_to_char)
+    }
+
+    /// Text drawing of grid, with given origin and blank character
+    fn render_with(&self, options: &TextOptions<T>, map_value_to_char: impl Fn(&Self::Value) -> char) -> String {
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return String::new()
+        };
+
+        let origin = options.origin.unwrap_or(bounds.min);
+        let (min_x, max_x): (isize, isize) = (origin.x.into(), bounds.max.x.into());
+        let (min_y, max_y): (isize, isize) = (origin.y.into(), bounds.max.y.into());
+
+        let mut lines = vec![];
+        for y in min_y..max_y + 1 {
+            let mut line: String = (min_x..max_x + 1)
+                .map(|x| match self.get_value(&Coordinate { x: T::from(x), y: T::from(y) }) {
+                    Some(value) => map_value_to_char(value),
+                    None => options.blank
+                })
+                .collect();
+            if options.skip_blanks {
+                line.truncate(line.trim_end_matches(options.blank).len());
+            }
+            lines.push(line);
+        }
+
+        lines.join("\n")
+    }
+}
+
+const EMPTY_GRID_BOUNDS: &str = "Empty grid has no bounds";
+
+/// A 2d grid implemented as an hash map of locations, keeping track of its bounds as locations are added (and recomputing them on demand
+/// after locations are removed)
 pub struct Grid<T, V> {
-    locations: HashMap<Coordinate<T>, V>
+    locations: BoundedMap<Coordinate<T>, V, Rect<T>>
 }
 
 impl<T, V> Grid<T, V>
@@ -245,7 +465,7 @@ where
     V: Eq
 {
     pub fn new() -> Grid<T, V> {
-        Grid { locations: HashMap::new() }
+        Grid { locations: BoundedMap::new() }
     }
 
     pub fn from(grid: &Grid<T, V>) -> Grid<T, V>
@@ -256,219 +476,345 @@ where
         Grid { locations: grid.locations.clone() }
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.locations.is_empty()
+    /// Grid of text characters mapped to values, first character being at `(0, 0)` (characters mapped to `None` are skipped)
+    pub fn parse(text: &str, map_char_to_value: impl FnMut(char) -> Option<V>) -> Grid<T, V> {
+        Grid::parse_with(text, &TextOptions::default(), map_char_to_value)
     }
 
-    pub fn size(&self) -> usize {
-        self.locations.len()
+    /// Grid of text characters mapped to values, with given origin and blank character
+    pub fn parse_with(text: &str, options: &TextOptions<T>, mut map_char_to_value: impl FnMut(char) -> Option<V>) -> Grid<T, V> {
+        let mut grid = Grid::new();
+        for_each_text_location(text, options, |location, char| {
+            if let Some(value) = map_char_to_value(char) {
+                grid.locations.insert(location, value);
+            }
+        });
+
+        grid
     }
 
-    pub fn add_location(&mut self, location: Coordinate<T>, value: V) -> Option<V> {
-        return self.locations.insert(location, value);
+    /// Grid of text characters mapped to values, first character being at `(0, 0)`, with a mapping rejecting invalid characters (error
+    /// points at first invalid character, with message returned by mapping)
+    pub fn try_parse(text: &str, mut map_char_to_value: impl FnMut(char) -> Result<Option<V>, String>) -> Result<Grid<T, V>, ParseError> {
+        let mut grid = Grid::new();
+        for (line_idx, line) in text.lines().enumerate() {
+            for (column_idx, char) in line.chars().enumerate() {
+                match map_char_to_value(char) {
+                    Ok(Some(value)) => {
+                        grid.locations.insert(Coordinate { x: T::from(column_idx as isize), y: T::from(line_idx as isize) }, value);
+                    },
+                    Ok(None) => {},
+                    Err(message) => return Err(ParseError::new(line_idx, line, column_idx, 1, &message))
+                }
+            }
+        }
+
+        Ok(grid)
     }
 
-    pub fn get_value(&self, location: &Coordinate<T>) -> Option<&V> {
-        self.locations.get(location)
+    /// Every location with its value, in no particular order
+    pub fn iter(&self) -> hash_map::Iter<'_, Coordinate<T>, V> {
+        self.locations.iter()
     }
 
-    pub fn count_values(&self, value: &V) -> usize {
-        self.locations.values().filter(|v| *v == value).count()
+    /// Every location with its mutable value, in no particular order
+    pub fn iter_mut(&mut self) -> hash_map::IterMut<'_, Coordinate<T>, V> {
+        self.locations.iter_mut()
     }
 
-    pub fn get_locations_with_value(&self, value: &V) -> Vec<Coordinate<T>> {
-        let (min_x, max_x): (isize, isize) = (self.min_x().into(), self.max_x().into());
-        let (min_y, max_y): (isize, isize) = (self.min_y().into(), self.max_y().into());
+    /// Location entry for in-place update (bounds are recomputed on demand if location was not in grid yet)
+    pub fn entry(&mut self, location: Coordinate<T>) -> hash_map::Entry<'_, Coordinate<T>, V> {
+        self.locations.entry(location)
+    }
+}
 
-        let mut locations = vec![];
-        for y in min_y..max_y + 1 {
-            for x in min_x..max_x + 1 {
-                let location = Coordinate { x: T::from(x), y: T::from(y) };
-                match self.locations.get(&location) {
-                    Some(v) => {
-                        if v == value {
-                            locations.push(location);
-                        }
-                    },
-                    None => continue
-                }
-            }
+impl<T, V> Default for Grid<T, V>
+where
+    T: Ord + Copy + Hash + From<isize> + Into<isize>,
+    V: Eq
+{
+    fn default() -> Grid<T, V> {
+        Grid::new()
+    }
+}
+
+impl<T, V> Extend<(Coordinate<T>, V)> for Grid<T, V>
+where
+    T: Ord + Copy + Hash + From<isize> + Into<isize>,
+    V: Eq
+{
+    fn extend<I: IntoIterator<Item = (Coordinate<T>, V)>>(&mut self, locations: I) {
+        for (location, value) in locations {
+            self.locations.insert(location, value);
         }
+    }
+}
 
-        locations
+impl<T, V> FromIterator<(Coordinate<T>, V)> for Grid<T, V>
+where
+    T: Ord + Copy + Hash + From<isize> + Into<isize>,
+    V: Eq
+{
+    fn from_iter<I: IntoIterator<Item = (Coordinate<T>, V)>>(locations: I) -> Grid<T, V> {
+        let mut grid = Grid::new();
+        grid.extend(locations);
+        grid
     }
+}
 
-    pub fn get_locations_with_values(&self, values: Vec<&V>) -> Vec<Coordinate<T>> {
-        let (min_x, max_x): (isize, isize) = (self.min_x().into(), self.max_x().into());
-        let (min_y, max_y): (isize, isize) = (self.min_y().into(), self.max_y().into());
+impl<T, V> IntoIterator for Grid<T, V> {
+    type Item = (Coordinate<T>, V);
+    type IntoIter = hash_map::IntoIter<Coordinate<T>, V>;
 
-        let mut locations = vec![];
-        for y in min_y..max_y + 1 {
-            for x in min_x..max_x + 1 {
-                let location = Coordinate { x: T::from(x), y: T::from(y) };
-                match self.locations.get(&location) {
-                    Some(v) => {
-                        if values.contains(&v) {
-                            locations.push(location);
-                        }
-                    },
-                    None => continue
-                }
-            }
-        }
+    fn into_iter(self) -> Self::IntoIter {
+        self.locations.into_iter()
+    }
+}
+
+impl<T, V> GridLike for Grid<T, V>
+where
+    T: Ord + Copy + Hash + From<isize> + Into<isize> + Add<isize, Output = T> + Sub<isize, Output = T>,
+    V: Eq
+{
+    type Location = Coordinate<T>;
+    type Value = V;
 
-        locations
+    fn size(&self) -> usize {
+        self.locations.len()
     }
 
-    pub fn get_mapped_locations_with_value(&self, value: &V) -> Vec<Coordinate<T>> {
-        self.locations.iter().filter(|(_, v)| *v == value).map(|(l, _)| Coordinate { x: l.x, y: l.y }).collect()
+    fn add_location(&mut self, location: Coordinate<T>, value: V) -> Option<V> {
+        self.locations.insert(location, value)
     }
 
-    pub fn keep_only_matching_locations(&mut self, f: &dyn Fn(&Coordinate<T>, &mut V) -> bool) {
-        self.locations.retain(f);
+    fn remove_location(&mut self, location: &Coordinate<T>) -> Option<V> {
+        self.locations.remove(location)
     }
 
-    pub fn min_x(&self) -> T {
-        self.locations.keys().min_by_key(|l| l.x).unwrap().x
+    fn get_value(&self, location: &Coordinate<T>) -> Option<&V> {
+        self.locations.get(location)
     }
 
-    pub fn min_y(&self) -> T {
-        self.locations.keys().min_by_key(|l| l.y).unwrap().y
+    fn get_value_mut(&mut self, location: &Coordinate<T>) -> Option<&mut V> {
+        self.locations.get_mut(location)
     }
 
-    pub fn max_x(&self) -> T {
-        self.locations.keys().max_by_key(|l| l.x).unwrap().x
+    fn locations(&self) -> Box<dyn Iterator<Item = (Coordinate<T>, &V)> + '_> {
+        Box::new(self.locations.iter().map(|(l, v)| (*l, v)))
     }
 
-    pub fn max_y(&self) -> T {
-        self.locations.keys().max_by_key(|l| l.y).unwrap().y
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&Coordinate<T>, &mut V) -> bool
+    {
+        self.locations.retain(f);
     }
 
-    pub fn width(&self) -> T {
-        let (min_x, max_x): (isize, isize) = (self.min_x().into(), self.max_x().into());
+    fn neighbors(&self, location: &Coordinate<T>) -> Vec<Coordinate<T>> {
+        get_adjacent_orthogonal_locations(location)
+    }
+}
 
-        if max_x < 0 || min_x > 0 {
-            T::from(max_x - min_x)
-        }
-        else {
-            T::from(max_x - min_x + 1) // Adding 1 for zero
-        }
+impl<T, V> GridMap<T> for Grid<T, V>
+where
+    T: Ord + Copy + Hash + From<isize> + Into<isize> + Add<isize, Output = T> + Sub<isize, Output = T>,
+    V: Eq
+{
+    fn bounds(&self) -> Option<Rect<T>> {
+        self.locations.bounds()
     }
+}
 
-    pub fn height(&self) -> T {
-        let (min_y, max_y): (isize, isize) = (self.min_y().into(), self.max_y().into());
+/// A 2d grid implemented as a dense array of locations, for fixed rectangular maps (bounds only contain added locations, like sparse grid
+/// ones, and are kept up to date the same way)
+pub struct DenseGrid<T, V> {
+    origin: Coordinate<T>,
+    width: usize,
+    height: usize,
+    values: Vec<Option<V>>,
+    size: usize,
+    bounds: Cell<Option<Rect<T>>>,
+    outdated_bounds: Cell<bool>
+}
 
-        if max_y < 0 || min_y > 0 {
-            T::from(max_y - min_y)
+impl<T, V> DenseGrid<T, V>
+where
+    T: Ord + Copy + Hash + From<isize> + Into<isize>,
+    V: Eq
+{
+    /// Constructor, from top left location and dimensions (adding a location outside of them panics)
+    pub fn new(origin: Coordinate<T>, width: usize, height: usize) -> DenseGrid<T, V> {
+        DenseGrid {
+            origin,
+            width,
+            height,
+            values: (0..width * height).map(|_| None).collect(),
+            size: 0,
+            bounds: Cell::new(None),
+            outdated_bounds: Cell::new(false)
         }
-        else {
-            T::from(max_y - min_y + 1) // Adding 1 for zero
+    }
+
+    pub fn from(grid: &DenseGrid<T, V>) -> DenseGrid<T, V>
+    where
+        V: Copy
+    {
+        DenseGrid {
+            origin: grid.origin,
+            width: grid.width,
+            height: grid.height,
+            values: grid.values.clone(),
+            size: grid.size,
+            bounds: grid.bounds.clone(),
+            outdated_bounds: grid.outdated_bounds.clone()
         }
     }
 
-    pub fn print(&self, empty_value: &V, map_value_to_char: &dyn Fn(&V) -> &str) {
-        let (min_x, max_x): (isize, isize) = (self.min_x().into(), self.max_x().into());
-        let (min_y, max_y): (isize, isize) = (self.min_y().into(), self.max_y().into());
-        let width = self.width().into();
-        let separator = (0..width + 2).map(|_| "-").collect::<String>();
+    /// Grid of text characters mapped to values, sized after text, first character being at `(0, 0)` (characters mapped to `None` are skipped)
+    pub fn parse(text: &str, map_char_to_value: impl FnMut(char) -> Option<V>) -> DenseGrid<T, V> {
+        DenseGrid::parse_with(text, &TextOptions::default(), map_char_to_value)
+    }
 
-        println!("{separator}");
-        for y in min_y..max_y + 1 {
-            let mut row = String::from("");
-            for x in min_x..max_x + 1 {
-                row.push_str(map_value_to_char(self.locations.get(&Coordinate { x: T::from(x), y: T::from(y) }).unwrap_or(empty_value)));
+    /// Grid of text characters mapped to values, sized after text, with given origin and blank character
+    pub fn parse_with(text: &str, options: &TextOptions<T>, mut map_char_to_value: impl FnMut(char) -> Option<V>) -> DenseGrid<T, V> {
+        let width = text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        let mut grid = DenseGrid::new(options.origin.unwrap_or(Coordinate { x: T::from(0), y: T::from(0) }), width, text.lines().count());
+        for_each_text_location(text, options, |location, char| {
+            if let Some(value) = map_char_to_value(char) {
+                let idx = grid.index(&location).unwrap();
+                if grid.values[idx].replace(value).is_none() {
+                    grid.size += 1;
+                }
+                grid.extend_bounds(&location);
             }
-            println!("|{}|", row);
-        }
-        println!("{separator}");
+        });
+
+        grid
     }
-}
 
-/// A 2d path of locations
-#[derive(Debug, Eq, PartialEq, Hash)]
-pub struct Path<T> {
-    pub locations: Vec<Coordinate<T>>
-}
+    /// Buffer index of location, if inside grid
+    fn index(&self, location: &Coordinate<T>) -> Option<usize> {
+        let x: isize = location.x.into() - self.origin.x.into();
+        let y: isize = location.y.into() - self.origin.y.into();
 
-impl<T> Path<T>
-where
-    T: Copy
-{
-    pub fn new(location: &Coordinate<T>) -> Path<T> {
-        Path { locations: vec![location.clone()] }
+        match x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+            true => Some(y as usize * self.width + x as usize),
+            false => None
+        }
     }
 
-    pub fn from(path: &Path<T>) -> Path<T> {
-        Path { locations: path.locations.clone() }
+    fn location(&self, idx: usize) -> Coordinate<T> {
+        let (x, y) = ((idx % self.width) as isize, (idx / self.width) as isize);
+        Coordinate { x: T::from(self.origin.x.into() + x), y: T::from(self.origin.y.into() + y) }
     }
-}
 
-/// A Path target location or value
-pub enum PathTarget<T, V> {
-    Location(Coordinate<T>),
-    Value(V)
+    /// Grow bounds to contain added location (unless they are already outdated and will be recomputed on demand)
+    fn extend_bounds(&self, location: &Coordinate<T>) {
+        if !self.outdated_bounds.get() {
+            self.bounds.set(Some(match self.bounds.get() {
+                Some(bounds) => bounds.extend(location),
+                None => Rect::new(*location)
+            }));
+        }
+    }
 }
 
-/// Find first shortest path found between start location and path target
-pub fn find_shortest_path<T, V>(
-    map: &Grid<T, V>,
-    start: &Coordinate<T>,
-    goal: &PathTarget<T, V>,
-    location_validator: Option<Box<dyn Fn(&V, &V) -> bool>>
-) -> Option<Path<T>>
+impl<T, V> GridLike for DenseGrid<T, V>
 where
     T: Ord + Copy + Hash + From<isize> + Into<isize> + Add<isize, Output = T> + Sub<isize, Output = T>,
     V: Eq
 {
-    let mut visited_locations: HashSet<Coordinate<T>> = HashSet::new();
-    let mut paths_to_explored: Vec<Path<T>> = vec![Path::new(start)];
-
-    let mut path_size = 0;
-    while paths_to_explored.len() > 0 {
-        path_size += 1;
-        println!("Path size: {path_size}");
-
-        let current_paths: Vec<Path<T>> = paths_to_explored.drain(..).collect();
-
-        for path in current_paths {
-            let current_location = path.locations.last().unwrap();
-            let current_value = map.get_value(&current_location).unwrap();
-
-            let adjacent_locations = get_adjacent_orthogonal_locations(&current_location);
-            let next_locations: Vec<Coordinate<T>> = adjacent_locations
-                .into_iter()
-                // Removing locations outside map
-                .filter(|l| map.get_value(&l) != None)
-                // Removing already visited locations
-                .filter(|l| visited_locations.contains(&l) == false)
-                .filter(|l| match &location_validator {
-                    // Removing invalid locations
-                    Some(validator) => (validator)(current_value, map.get_value(&l).unwrap()),
-                    None => true
-                })
-                .collect();
+    type Location = Coordinate<T>;
+    type Value = V;
 
-            for location in next_locations.into_iter() {
-                let mut next_path = Path::from(&path);
-                next_path.locations.push(location.clone());
+    fn size(&self) -> usize {
+        self.size
+    }
 
-                if match goal {
-                    PathTarget::Location(goal_location) => &location == goal_location,
-                    PathTarget::Value(goal_value) => map.get_value(&location).unwrap() == goal_value
-                } {
-                    // Found path to goal
-                    return Some(next_path);
-                }
+    fn add_location(&mut self, location: Coordinate<T>, value: V) -> Option<V> {
+        let (x, y): (isize, isize) = (location.x.into(), location.y.into());
+        let idx = match self.index(&location) {
+            Some(idx) => idx,
+            None => panic!("Location ({x}, {y}) outside of {}x{} dense grid", self.width, self.height)
+        };
+
+        let previous_value = self.values[idx].replace(value);
+        if previous_value.is_none() {
+            self.size += 1;
+            self.extend_bounds(&location);
+        }
+
+        previous_value
+    }
+
+    fn remove_location(&mut self, location: &Coordinate<T>) -> Option<V> {
+        let value = self.index(location).and_then(|idx| self.values[idx].take());
+        if value.is_some() {
+            self.size -= 1;
+            if self.bounds.get().map(|b| b.is_on_border(location)).unwrap_or(true) {
+                self.outdated_bounds.set(true);
+            }
+        }
+
+        value
+    }
+
+    fn get_value(&self, location: &Coordinate<T>) -> Option<&V> {
+        self.index(location).and_then(|idx| self.values[idx].as_ref())
+    }
+
+    fn get_value_mut(&mut self, location: &Coordinate<T>) -> Option<&mut V> {
+        self.index(location).and_then(|idx| self.values[idx].as_mut())
+    }
+
+    fn locations(&self) -> Box<dyn Iterator<Item = (Coordinate<T>, &V)> + '_> {
+        Box::new(self.values.iter().enumerate().filter_map(|(idx, v)| v.as_ref().map(|v| (self.location(idx), v))))
+    }
 
-                visited_locations.insert(location);
-                paths_to_explored.push(next_path);
+    fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Coordinate<T>, &mut V) -> bool
+    {
+        for idx in 0..self.values.len() {
+            let location = self.location(idx);
+            if let Some(value) = self.values[idx].as_mut() {
+                if !f(&location, value) {
+                    self.values[idx] = None;
+                    self.size -= 1;
+                    self.outdated_bounds.set(true);
+                }
             }
         }
     }
 
-    None
+    fn neighbors(&self, location: &Coordinate<T>) -> Vec<Coordinate<T>> {
+        get_adjacent_orthogonal_locations(location)
+    }
 }
 
+impl<T, V> GridMap<T> for DenseGrid<T, V>
+where
+    T: Ord + Copy + Hash + From<isize> + Into<isize> + Add<isize, Output = T> + Sub<isize, Output = T>,
+    V: Eq
+{
+    fn bounds(&self) -> Option<Rect<T>> {
+        if self.outdated_bounds.replace(false) {
+            self.bounds.set(self.locations().fold(None, |bounds, (location, _)| match bounds {
+                Some(bounds) => Some(bounds.extend(&location)),
+                None => Some(Rect::new(location))
+            }));
+        }
+
+        self.bounds.get()
+    }
+}
+
+/// A 2d path of locations
+pub type Path<T> = grid::Path<Coordinate<T>>;
+
+/// A 2d path target location or value
+pub type PathTarget<T, V> = grid::PathTarget<Coordinate<T>, V>;
+
 /// Print a path on top of grid map first shortest path found between start location and path target
 pub fn print_path<T, V>(
     path: &Path<T>,
@@ -479,9 +825,13 @@ pub fn print_path<T, V>(
     empty_value: &V,
     map_value_to_char: &dyn Fn(&V) -> &str
 ) where
-    T: Ord + Copy + Hash + From<isize> + Into<isize>,
+    T: Ord + Copy + Hash + From<isize> + Into<isize> + Add<isize, Output = T> + Sub<isize, Output = T>,
     V: Copy + Eq
 {
+    if !log::enabled(Level::Debug) {
+        return;
+    }
+
     // New grid to draw on
     let mut new_map = Grid::from(map);
 
@@ -505,3 +855,379 @@ pub fn print_path<T, V>(
 
     new_map.print(empty_value, map_value_to_char);
 }
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_snapshot;
+    use rand::Rng;
+    use std::collections::HashMap;
+
+    const NB_RANDOM_CASES: usize = 200;
+
+    const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    fn random_location(rng: &mut impl Rng) -> Coordinate<isize> {
+        Coordinate { x: rng.gen_range(-100..100), y: rng.gen_range(-100..100) }
+    }
+
+    /// Random map of open (`.`) and wall (`#`) locations, at random offset (so that its range does not always contain zero)
+    fn random_map(rng: &mut impl Rng, wall_ratio: f64) -> Grid<isize, char> {
+        let origin = random_location(rng);
+        let (width, height) = (rng.gen_range(1..15), rng.gen_range(1..15));
+
+        let mut map = Grid::new();
+        for y in origin.y..origin.y + height {
+            for x in origin.x..origin.x + width {
+                map.add_location(
+                    Coordinate { x, y },
+                    if rng.gen_bool(wall_ratio) {
+                        '#'
+                    }
+                    else {
+                        '.'
+                    }
+                );
+            }
+        }
+
+        map
+    }
+
+    fn random_open_location(rng: &mut impl Rng, map: &Grid<isize, char>) -> Option<Coordinate<isize>> {
+        let locations = map.get_locations_with_value(&'.');
+        match locations.is_empty() {
+            true => None,
+            false => Some(locations[rng.gen_range(0..locations.len())])
+        }
+    }
+
+    #[test]
+    fn test_manhattan_distance() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..NB_RANDOM_CASES {
+            let (a, b, c) = (random_location(&mut rng), random_location(&mut rng), random_location(&mut rng));
+
+            assert_eq!(0, a.manhattan_distance(&a));
+            assert_eq!(a.manhattan_distance(&b), b.manhattan_distance(&a));
+            assert!(a.manhattan_distance(&c) <= a.manhattan_distance(&b) + b.manhattan_distance(&c));
+            for location in get_adjacent_orthogonal_locations(&a) {
+                assert_eq!(1, a.manhattan_distance(&location));
+            }
+            for location in get_adjacent_diagonal_locations(&a) {
+                assert_eq!(2, a.manhattan_distance(&location));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotations() {
+        for direction in DIRECTIONS {
+            let mut rotated = direction.clone();
+            for _ in 0..4 {
+                rotated = get_direction_after_rotation(&rotated, &Rotation::Clockwise);
+            }
+            assert_eq!(direction, rotated);
+
+            let clockwise = get_direction_after_rotation(&direction, &Rotation::Clockwise);
+            assert_eq!(direction, get_direction_after_rotation(&clockwise, &Rotation::CounterClockwise));
+            assert_eq!(get_opposite_direction(&direction), get_direction_after_rotation(&clockwise, &Rotation::Clockwise));
+            assert_eq!(direction, get_opposite_direction(&get_opposite_direction(&direction)));
+            assert_ne!(direction, get_opposite_direction(&direction));
+        }
+    }
+
+    #[test]
+    fn test_direction_from_adjacent_locations() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..NB_RANDOM_CASES {
+            let location = random_location(&mut rng);
+            for direction in DIRECTIONS {
+                let adjacent_location = get_adjacent_locations_in_direction(&location, &direction);
+
+                assert_eq!(direction, get_direction_from_adjacent_locations(&location, &adjacent_location));
+                assert_eq!(get_opposite_direction(&direction), get_direction_from_adjacent_locations(&adjacent_location, &location));
+            }
+        }
+    }
+
+    #[test]
+    fn test_width_height() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..NB_RANDOM_CASES {
+            let map = random_map(&mut rng, 0.0);
+
+            assert_eq!(map.max_x() - map.min_x() + 1, map.width());
+            assert_eq!(map.max_y() - map.min_y() + 1, map.height());
+            assert_eq!(map.size(), (map.width() * map.height()) as usize);
+        }
+
+        // Ranges not containing zero
+        let mut map: Grid<isize, char> = Grid::new();
+        map.add_location(Coordinate { x: 2, y: -5 }, '.');
+        map.add_location(Coordinate { x: 4, y: -3 }, '.');
+        assert_eq!((3, 3), (map.width(), map.height()));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..NB_RANDOM_CASES {
+            let mut grid: Grid<isize, bool> = Grid::new();
+            assert_eq!(None, grid.bounds());
+            assert_eq!((0, 0), (grid.width(), grid.height()));
+
+            // Bounds kept up to date as locations are added, then removed
+            for _ in 0..rng.gen_range(1..20) {
+                grid.add_location(random_location(&mut rng), rng.gen_bool(0.5));
+                assert_eq!(Rect::from_locations(grid.iter().map(|(location, _)| location)), grid.bounds());
+            }
+            grid.keep_only_matching_locations(&|_, value| *value);
+            assert_eq!(Rect::from_locations(grid.iter().map(|(location, _)| location)), grid.bounds());
+            grid.add_location(random_location(&mut rng), true);
+            assert_eq!(Rect::from_locations(grid.iter().map(|(location, _)| location)), grid.bounds());
+
+            let bounds = grid.bounds().unwrap();
+            assert!(grid.locations().all(|(location, _)| bounds.contains(&location)));
+            assert_eq!((bounds.width(), bounds.height()), (grid.width(), grid.height()));
+        }
+
+        assert_eq!("--\n--", Grid::<isize, bool>::new().draw(&false, &|_| "#"));
+    }
+
+    #[test]
+    fn test_map_operations() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..NB_RANDOM_CASES {
+            // Same content and bounds as hash map, whatever the operations
+            let mut locations: HashMap<Coordinate<isize>, usize> =
+                (0..rng.gen_range(1..20)).map(|_| (random_location(&mut rng), rng.gen_range(0..5))).collect();
+            let mut grid: Grid<isize, usize> = locations.clone().into_iter().collect();
+
+            let location = random_location(&mut rng);
+            *locations.entry(location).or_default() += 1;
+            *grid.entry(location).or_default() += 1;
+
+            let location = *locations.keys().next().unwrap();
+            assert_eq!(locations.remove(&location), grid.remove_location(&location));
+            assert_eq!(None, grid.remove_location(&location));
+
+            if let Some(location) = locations.keys().next().copied() {
+                *locations.get_mut(&location).unwrap() += 10;
+                *grid.get_value_mut(&location).unwrap() += 10;
+            }
+
+            locations.iter_mut().for_each(|(_, value)| *value *= 2);
+            grid.iter_mut().for_each(|(_, value)| *value *= 2);
+            locations.retain(|_, value| *value != 4);
+            grid.retain(|_, value| *value != 4);
+
+            let new_locations: Vec<(Coordinate<isize>, usize)> = (0..rng.gen_range(0..5)).map(|_| (random_location(&mut rng), 1)).collect();
+            locations.extend(new_locations.clone());
+            grid.extend(new_locations);
+
+            assert_eq!(locations.len(), grid.size());
+            assert!(grid.iter().all(|(location, value)| locations.get(location) == Some(value)));
+            assert_eq!(Rect::from_locations(locations.keys()), grid.bounds());
+        }
+    }
+
+    #[test]
+    fn test_parse_render() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..NB_RANDOM_CASES {
+            // Random holes in map, rendered as blanks
+            let mut map = random_map(&mut rng, 0.3);
+            map.retain(|_, _| rng.gen_bool(0.8));
+
+            let options = TextOptions { origin: map.bounds().map(|b| b.min), skip_blanks: rng.gen_bool(0.5), ..TextOptions::default() };
+            let text = map.render_with(&options, |value| *value);
+
+            // Same map once parsed back
+            let parsed_map: Grid<isize, char> = Grid::parse_with(&text, &options, |char| (char != options.blank).then_some(char));
+            assert_eq!(map.size(), parsed_map.size());
+            assert_eq!(map.bounds(), parsed_map.bounds());
+            assert!(map.iter().all(|(location, value)| parsed_map.get_value(location) == Some(value)));
+            assert_eq!(text, parsed_map.render_with(&options, |value| *value));
+
+            let dense_map: DenseGrid<isize, char> = DenseGrid::parse_with(&text, &options, |char| (char != options.blank).then_some(char));
+            assert_eq!(map.size(), dense_map.size());
+            assert!(map.iter().all(|(location, value)| dense_map.get_value(location) == Some(value)));
+        }
+
+        // Default layout, from (0, 0)
+        let map = Grid::parse("#.\n.#", |char| (char == '#').then_some(char));
+        assert_eq!(vec![Coordinate { x: 0, y: 0 }, Coordinate { x: 1, y: 1 }], map.get_locations_with_value(&'#'));
+        assert_eq!("# \n #", map.render(|value| *value));
+        assert_eq!("", Grid::<isize, char>::new().render(|value| *value));
+    }
+
+    #[test]
+    fn test_try_parse() {
+        let map_char = |char| match char {
+            '#' => Ok(Some(char)),
+            '.' => Ok(None),
+            _ => Err(format!("Invalid character \"{char}\""))
+        };
+
+        let map: Grid<isize, char> = Grid::try_parse("#.\n.#", map_char).unwrap();
+        assert_eq!(vec![Coordinate { x: 0, y: 0 }, Coordinate { x: 1, y: 1 }], map.get_locations_with_value(&'#'));
+        assert_eq!(2, map.size());
+
+        let error = Grid::<isize, char>::try_parse("#.\n.#x#", map_char).err().unwrap();
+        assert_eq!((2, 3, "Invalid character \"x\""), (error.line_number, error.column, &error.message[..]));
+    }
+
+    #[test]
+    fn test_find_shortest_path() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..NB_RANDOM_CASES {
+            let wall_ratio = if rng.gen_bool(0.5) {
+                0.0
+            }
+            else {
+                0.3
+            };
+            let map = random_map(&mut rng, wall_ratio);
+            let (start, goal) = match (random_open_location(&mut rng, &map), random_open_location(&mut rng, &map)) {
+                (Some(start), Some(goal)) if start != goal => (start, goal),
+                _ => continue
+            };
+
+            let path = find_shortest_path(&map, &start, &PathTarget::Location(goal), Some(Box::new(|_, next| *next != '#')));
+            let path = match (path, wall_ratio == 0.0) {
+                (Some(path), _) => path,
+                (None, false) => continue, // Goal may be walled off
+                (None, true) => panic!("No path found from {:?} to {:?} in open map", start, goal)
+            };
+
+            assert_eq!(Some(&start), path.locations.first());
+            assert_eq!(Some(&goal), path.locations.last());
+            for pair in path.locations.windows(2) {
+                assert_eq!(1, pair[0].manhattan_distance(&pair[1]));
+                assert_eq!(Some(&'.'), map.get_value(&pair[1]));
+            }
+
+            // Path length never beats manhattan distance, and matches it in open map
+            let nb_steps = (path.locations.len() - 1) as isize;
+            assert!(nb_steps >= start.manhattan_distance(&goal));
+            if wall_ratio == 0.0 {
+                assert_eq!(start.manhattan_distance(&goal), nb_steps);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dense_grid() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..NB_RANDOM_CASES {
+            let map = random_map(&mut rng, 0.3);
+            let origin = Coordinate { x: map.min_x(), y: map.min_y() };
+            let mut dense_map = DenseGrid::new(origin, map.width() as usize, map.height() as usize);
+            for (location, value) in map.locations() {
+                dense_map.add_location(location, *value);
+            }
+
+            // Same values, bounds and drawing as sparse grid
+            assert_eq!(map.size(), dense_map.size());
+            assert_eq!((map.min_x(), map.max_x(), map.min_y(), map.max_y()), (dense_map.min_x(), dense_map.max_x(), dense_map.min_y(), dense_map.max_y()));
+            assert_eq!((map.width(), map.height()), (dense_map.width(), dense_map.height()));
+            assert_eq!(map.get_locations_with_value(&'#'), dense_map.get_locations_with_value(&'#'));
+            assert_eq!(map.count_values(&'.'), dense_map.count_values(&'.'));
+            assert_eq!(
+                map.draw(&' ', &|v| if *v == '#' {
+                    "#"
+                }
+                else {
+                    "."
+                }),
+                dense_map.draw(&' ', &|v| if *v == '#' {
+                    "#"
+                }
+                else {
+                    "."
+                })
+            );
+            assert_eq!(None, dense_map.get_value(&Coordinate { x: origin.x - 1, y: origin.y }));
+
+            // Same shortest paths length
+            if let (Some(start), Some(goal)) = (random_open_location(&mut rng, &map), random_open_location(&mut rng, &map)) {
+                let path = find_shortest_path(&map, &start, &PathTarget::Location(goal.clone()), Some(Box::new(|_, next| *next != '#')));
+                let dense_path = find_shortest_path(&dense_map, &start, &PathTarget::Location(goal), Some(Box::new(|_, next| *next != '#')));
+                assert_eq!(path.map(|p| p.locations.len()), dense_path.map(|p| p.locations.len()));
+            }
+
+            // Bounds of remaining locations only
+            dense_map.keep_only_matching_locations(&|_, value| *value == '#');
+            let walls_map: Grid<isize, char> = map.iter().filter(|(_, value)| **value == '#').map(|(location, value)| (*location, *value)).collect();
+            assert_eq!(map.count_values(&'#'), dense_map.size());
+            assert_eq!(walls_map.bounds(), dense_map.bounds());
+            assert_eq!((walls_map.width(), walls_map.height()), (dense_map.width(), dense_map.height()));
+            if let Some(location) = dense_map.get_locations_with_value(&'#').first() {
+                assert_eq!(Some('#'), dense_map.remove_location(location));
+                assert_eq!(map.count_values(&'#') - 1, dense_map.size());
+            }
+        }
+    }
+
+    #[test]
+    fn test_dense_grid_bounds() {
+        let mut grid: DenseGrid<isize, char> = DenseGrid::new(Coordinate { x: -1, y: -1 }, 3, 3);
+        assert_eq!(None, grid.bounds());
+        assert_eq!((0, 0), (grid.width(), grid.height()));
+        assert_eq!("", grid.render(|value| *value));
+
+        grid.add_location(Coordinate { x: 0, y: 0 }, '#');
+        grid.add_location(Coordinate { x: 1, y: 0 }, '#');
+        assert_eq!(Some(Rect { min: Coordinate { x: 0, y: 0 }, max: Coordinate { x: 1, y: 0 } }), grid.bounds());
+        assert_eq!((2, 1), (grid.width(), grid.height()));
+        assert_eq!("##", grid.render(|value| *value));
+
+        grid.remove_location(&Coordinate { x: 0, y: 0 });
+        assert_eq!(Some(Rect::new(Coordinate { x: 1, y: 0 })), grid.bounds());
+
+        // Removing an inner location keeps bounds, removing a border one shrinks them
+        for (x, y) in [(-1, -1), (0, 0), (1, 1)] {
+            grid.add_location(Coordinate { x, y }, '#');
+        }
+        assert_eq!(Some(Rect { min: Coordinate { x: -1, y: -1 }, max: Coordinate { x: 1, y: 1 } }), grid.bounds());
+        grid.remove_location(&Coordinate { x: 0, y: 0 });
+        assert_eq!(Some(Rect { min: Coordinate { x: -1, y: -1 }, max: Coordinate { x: 1, y: 1 } }), grid.bounds());
+        grid.remove_location(&Coordinate { x: -1, y: -1 });
+        assert_eq!(Some(Rect { min: Coordinate { x: 1, y: 0 }, max: Coordinate { x: 1, y: 1 } }), grid.bounds());
+        assert_eq!((1, 2), (grid.width(), grid.height()));
+
+        grid.keep_only_matching_locations(&|location, _| location.y == 1);
+        assert_eq!(Some(Rect::new(Coordinate { x: 1, y: 1 })), grid.bounds());
+        grid.remove_location(&Coordinate { x: 1, y: 1 });
+        assert_eq!(None, grid.bounds());
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of 2x2 dense grid")]
+    fn test_dense_grid_outside_location() {
+        let mut grid: DenseGrid<isize, char> = DenseGrid::new(Coordinate { x: -1, y: -1 }, 2, 2);
+        grid.add_location(Coordinate { x: 0, y: 0 }, '#');
+        grid.add_location(Coordinate { x: 1, y: 0 }, '#');
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut grid: Grid<isize, char> = Grid::new();
+        for (x, y, value) in [(-1, -1, '#'), (0, 0, 'S'), (1, 0, '#'), (3, 1, 'E'), (2, 1, '#')] {
+            grid.add_location(Coordinate { x, y }, value);
+        }
+
+        assert_snapshot!(
+            "grid",
+            grid.draw(&'.', &|v| match v {
+                'S' => "S",
+                'E' => "E",
+                '#' => "#",
+                _ => "."
+            })
+        );
+    }
+}
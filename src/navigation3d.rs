@@ -1,6 +1,10 @@
+use crate::debug;
+pub use crate::grid::find_shortest_path;
+use crate::grid::{self, BoundedMap, Bounds, GridLike};
+use crate::log::{self, Level};
 use num::Signed;
 use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
+use std::collections::hash_map;
 use std::hash::{Hash, Hasher};
 use std::ops::{Add, Sub};
 
@@ -98,6 +102,88 @@ where
     }
 }
 
+/// A 3d box, both corners included
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Cuboid<T> {
+    pub min: Coordinate3D<T>,
+    pub max: Coordinate3D<T>
+}
+
+impl<T> Cuboid<T>
+where
+    T: Ord + Copy
+{
+    /// Box of a single location
+    pub fn new(location: Coordinate3D<T>) -> Cuboid<T> {
+        Cuboid { min: location, max: location }
+    }
+
+    /// Smallest box containing every location (`None` without locations)
+    pub fn from_locations<'a>(mut locations: impl Iterator<Item = &'a Coordinate3D<T>>) -> Option<Cuboid<T>>
+    where
+        T: 'a
+    {
+        let first_location = locations.next()?;
+        Some(locations.fold(Cuboid::new(*first_location), |cuboid, location| cuboid.extend(location)))
+    }
+
+    /// Smallest box containing both box and location
+    pub fn extend(&self, location: &Coordinate3D<T>) -> Cuboid<T> {
+        Cuboid {
+            min: Coordinate3D { x: self.min.x.min(location.x), y: self.min.y.min(location.y), z: self.min.z.min(location.z) },
+            max: Coordinate3D { x: self.max.x.max(location.x), y: self.max.y.max(location.y), z: self.max.z.max(location.z) }
+        }
+    }
+
+    pub fn contains(&self, location: &Coordinate3D<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&location.x) && (self.min.y..=self.max.y).contains(&location.y) && (self.min.z..=self.max.z).contains(&location.z)
+    }
+
+    /// Whether location is on one of the box faces (e.g: removing it may shrink bounds)
+    pub fn is_on_border(&self, location: &Coordinate3D<T>) -> bool {
+        location.x == self.min.x
+            || location.x == self.max.x
+            || location.y == self.min.y
+            || location.y == self.max.y
+            || location.z == self.min.z
+            || location.z == self.max.z
+    }
+}
+
+impl<T> Cuboid<T>
+where
+    T: Copy + From<isize> + Into<isize>
+{
+    pub fn width(&self) -> T {
+        T::from(self.max.x.into() - self.min.x.into() + 1) // Both bounds included
+    }
+
+    pub fn height(&self) -> T {
+        T::from(self.max.y.into() - self.min.y.into() + 1) // Both bounds included
+    }
+
+    pub fn depth(&self) -> T {
+        T::from(self.max.z.into() - self.min.z.into() + 1) // Both bounds included
+    }
+}
+
+impl<T> Bounds<Coordinate3D<T>> for Cuboid<T>
+where
+    T: Ord + Copy
+{
+    fn new(location: Coordinate3D<T>) -> Cuboid<T> {
+        Cuboid::new(location)
+    }
+
+    fn extend(&self, location: &Coordinate3D<T>) -> Cuboid<T> {
+        Cuboid::extend(self, location)
+    }
+
+    fn is_on_border(&self, location: &Coordinate3D<T>) -> bool {
+        Cuboid::is_on_border(self, location)
+    }
+}
+
 /// A 3d direction
 #[derive(Debug, Eq, PartialEq, Hash)]
 pub enum Direction {
@@ -124,9 +210,56 @@ where
     ]
 }
 
-/// A 2d grid implemented as an hash map of locations
+const EMPTY_GRID_BOUNDS: &str = "Empty grid has no bounds";
+
+/// A 3d grid implemented as an hash map of locations, keeping track of its bounds as locations are added (and recomputing them on demand
+/// after locations are removed)
 pub struct Grid3D<T, V> {
-    locations: HashMap<Coordinate3D<T>, V>
+    locations: BoundedMap<Coordinate3D<T>, V, Cuboid<T>>
+}
+
+impl<T, V> GridLike for Grid3D<T, V>
+where
+    T: Ord + Copy + Hash + Add<isize, Output = T> + Sub<isize, Output = T>,
+    V: Eq
+{
+    type Location = Coordinate3D<T>;
+    type Value = V;
+
+    fn size(&self) -> usize {
+        self.locations.len()
+    }
+
+    fn add_location(&mut self, location: Coordinate3D<T>, value: V) -> Option<V> {
+        self.locations.insert(location, value)
+    }
+
+    fn remove_location(&mut self, location: &Coordinate3D<T>) -> Option<V> {
+        self.locations.remove(location)
+    }
+
+    fn get_value(&self, location: &Coordinate3D<T>) -> Option<&V> {
+        self.locations.get(location)
+    }
+
+    fn get_value_mut(&mut self, location: &Coordinate3D<T>) -> Option<&mut V> {
+        self.locations.get_mut(location)
+    }
+
+    fn locations(&self) -> Box<dyn Iterator<Item = (Coordinate3D<T>, &V)> + '_> {
+        Box::new(self.locations.iter().map(|(l, v)| (*l, v)))
+    }
+
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&Coordinate3D<T>, &mut V) -> bool
+    {
+        self.locations.retain(f);
+    }
+
+    fn neighbors(&self, location: &Coordinate3D<T>) -> Vec<Coordinate3D<T>> {
+        get_adjacent_orthogonal_locations(location)
+    }
 }
 
 impl<T, V> Grid3D<T, V>
@@ -135,7 +268,7 @@ where
     V: Eq
 {
     pub fn new() -> Grid3D<T, V> {
-        Grid3D { locations: HashMap::new() }
+        Grid3D { locations: BoundedMap::new() }
     }
 
     pub fn from(grid: &Grid3D<T, V>) -> Grid3D<T, V>
@@ -146,217 +279,349 @@ where
         Grid3D { locations: grid.locations.clone() }
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.locations.is_empty()
-    }
-
-    pub fn size(&self) -> usize {
-        self.locations.len()
+    /// Every location with its value, in no particular order
+    pub fn iter(&self) -> hash_map::Iter<'_, Coordinate3D<T>, V> {
+        self.locations.iter()
     }
 
-    pub fn add_location(&mut self, location: Coordinate3D<T>, value: V) -> Option<V> {
-        return self.locations.insert(location, value);
+    /// Every location with its mutable value, in no particular order
+    pub fn iter_mut(&mut self) -> hash_map::IterMut<'_, Coordinate3D<T>, V> {
+        self.locations.iter_mut()
     }
 
-    pub fn get_value(&self, location: &Coordinate3D<T>) -> Option<&V> {
-        self.locations.get(location)
-    }
-
-    pub fn count_values(&self, value: &V) -> usize {
-        self.locations.values().filter(|v| *v == value).count()
-    }
-
-    pub fn get_locations_with_value(&self, value: &V) -> Vec<Coordinate3D<T>> {
-        let (min_x, max_x): (isize, isize) = (self.min_x().into(), self.max_x().into());
-        let (min_y, max_y): (isize, isize) = (self.min_y().into(), self.max_y().into());
-        let (min_z, max_z): (isize, isize) = (self.min_z().into(), self.max_z().into());
-
-        let mut locations = vec![];
-        for z in min_z..max_z + 1 {
-            for y in min_y..max_y + 1 {
-                for x in min_x..max_x + 1 {
-                    let location = Coordinate3D { x: T::from(x), y: T::from(y), z: T::from(z) };
-                    match self.locations.get(&location) {
-                        Some(v) => {
-                            if v == value {
-                                locations.push(location);
-                            }
-                        },
-                        None => continue
-                    }
-                }
-            }
-        }
-
-        locations
+    /// Location entry for in-place update (bounds are recomputed on demand if location was not in grid yet)
+    pub fn entry(&mut self, location: Coordinate3D<T>) -> hash_map::Entry<'_, Coordinate3D<T>, V> {
+        self.locations.entry(location)
     }
 
-    pub fn get_mapped_locations_with_value(&self, value: &V) -> Vec<Coordinate3D<T>> {
-        self.locations.iter().filter(|(_, v)| *v == value).map(|(l, _)| Coordinate3D { x: l.x, y: l.y, z: l.z }).collect()
+    /// Smallest box containing every location (`None` for empty grid)
+    pub fn bounds(&self) -> Option<Cuboid<T>> {
+        self.locations.bounds()
     }
 
+    /// Panics on empty grid (see `bounds`)
     pub fn min_x(&self) -> T {
-        self.locations.keys().min_by_key(|l| l.x).unwrap().x
+        self.bounds().expect(EMPTY_GRID_BOUNDS).min.x
     }
 
+    /// Panics on empty grid (see `bounds`)
     pub fn min_y(&self) -> T {
-        self.locations.keys().min_by_key(|l| l.y).unwrap().y
+        self.bounds().expect(EMPTY_GRID_BOUNDS).min.y
     }
 
+    /// Panics on empty grid (see `bounds`)
     pub fn min_z(&self) -> T {
-        self.locations.keys().min_by_key(|l| l.z).unwrap().z
+        self.bounds().expect(EMPTY_GRID_BOUNDS).min.z
     }
 
+    /// Panics on empty grid (see `bounds`)
     pub fn max_x(&self) -> T {
-        self.locations.keys().max_by_key(|l| l.x).unwrap().x
+        self.bounds().expect(EMPTY_GRID_BOUNDS).max.x
     }
 
+    /// Panics on empty grid (see `bounds`)
     pub fn max_y(&self) -> T {
-        self.locations.keys().max_by_key(|l| l.y).unwrap().y
+        self.bounds().expect(EMPTY_GRID_BOUNDS).max.y
     }
 
+    /// Panics on empty grid (see `bounds`)
     pub fn max_z(&self) -> T {
-        self.locations.keys().max_by_key(|l| l.z).unwrap().z
+        self.bounds().expect(EMPTY_GRID_BOUNDS).max.z
     }
 
+    /// Zero for empty grid
     pub fn width(&self) -> T {
-        let (min_x, max_x): (isize, isize) = (self.min_x().into(), self.max_x().into());
-
-        if max_x < 0 || min_x > 0 {
-            T::from(max_x - min_x)
-        }
-        else {
-            T::from(max_x - min_x + 1) // Adding 1 for zero
-        }
+        self.bounds().map(|b| b.width()).unwrap_or(T::from(0))
     }
 
+    /// Zero for empty grid
     pub fn height(&self) -> T {
-        let (min_y, max_y): (isize, isize) = (self.min_y().into(), self.max_y().into());
-
-        if max_y < 0 || min_y > 0 {
-            T::from(max_y - min_y)
-        }
-        else {
-            T::from(max_y - min_y + 1) // Adding 1 for zero
-        }
+        self.bounds().map(|b| b.height()).unwrap_or(T::from(0))
     }
 
+    /// Zero for empty grid
     pub fn depth(&self) -> T {
-        let (min_z, max_z): (isize, isize) = (self.min_z().into(), self.max_z().into());
+        self.bounds().map(|b| b.depth()).unwrap_or(T::from(0))
+    }
 
-        if max_z < 0 || min_z > 0 {
-            T::from(max_z - min_z)
+    pub fn print(&self, empty_value: &V, map_value_to_char: &dyn Fn(&V) -> &str) {
+        if !log::enabled(Level::Debug) {
+            return;
         }
-        else {
-            T::from(max_z - min_z + 1) // Adding 1 for zero
+
+        debug!("{}", self.draw(empty_value, map_value_to_char));
+    }
+
+    pub fn print_z_layer(&self, z: T, empty_value: &V, map_value_to_char: &dyn Fn(&V) -> &str) {
+        if !log::enabled(Level::Debug) {
+            return;
         }
+
+        debug!("{}", self.draw_z_layer(z, empty_value, map_value_to_char));
     }
 
-    pub fn print(&self, empty_value: &V, map_value_to_char: &dyn Fn(&V) -> &str) {
-        let (min_z, max_z): (isize, isize) = (self.min_z().into(), self.max_z().into());
+    /// Framed drawing of every z layer, preceded by its z value (e.g: for `print` or snapshot tests)
+    pub fn draw(&self, empty_value: &V, map_value_to_char: &dyn Fn(&V) -> &str) -> String {
+        // Empty grid has no layers
+        let (min_z, max_z): (isize, isize) = self.bounds().map(|b| (b.min.z.into(), b.max.z.into())).unwrap_or((0, -1));
 
+        let mut layers = vec![];
         for z in min_z..max_z + 1 {
-            println!("z = {z}");
-            self.print_z_layer(T::from(z), empty_value, map_value_to_char);
-            println!("");
+            layers.push(format!("z = {z}\n{}", self.draw_z_layer(T::from(z), empty_value, map_value_to_char)));
         }
+
+        layers.join("\n\n")
     }
 
-    pub fn print_z_layer(&self, z: T, empty_value: &V, map_value_to_char: &dyn Fn(&V) -> &str) {
-        let (min_x, max_x): (isize, isize) = (self.min_x().into(), self.max_x().into());
-        let (min_y, max_y): (isize, isize) = (self.min_y().into(), self.max_y().into());
-        let width = self.width().into();
+    /// Framed drawing of z layer, one row per line
+    pub fn draw_z_layer(&self, z: T, empty_value: &V, map_value_to_char: &dyn Fn(&V) -> &str) -> String {
+        let bounds = self.bounds();
+        let width = bounds.map(|b| b.width().into()).unwrap_or(0);
+
+        // Empty grid is drawn as an empty frame
+        let (min_x, max_x): (isize, isize) = bounds.map(|b| (b.min.x.into(), b.max.x.into())).unwrap_or((0, -1));
+        let (min_y, max_y): (isize, isize) = bounds.map(|b| (b.min.y.into(), b.max.y.into())).unwrap_or((0, -1));
         let separator = (0..width + 2).map(|_| "-").collect::<String>();
 
-        println!("{separator}");
+        let mut lines = vec![separator.clone()];
         for y in min_y..max_y + 1 {
             let mut row = String::from("");
             for x in min_x..max_x + 1 {
                 row.push_str(map_value_to_char(self.locations.get(&Coordinate3D { x: T::from(x), y: T::from(y), z }).unwrap_or(empty_value)));
             }
-            println!("|{}|", row);
+            lines.push(format!("|{}|", row));
         }
-        println!("{separator}");
+        lines.push(separator);
+
+        lines.join("\n")
     }
 }
 
-/// A 3d path of locations
-#[derive(Debug, Eq, PartialEq, Hash)]
-pub struct Path<T> {
-    pub locations: Vec<Coordinate3D<T>>
+impl<T, V> Default for Grid3D<T, V>
+where
+    T: Ord + Copy + Hash + From<isize> + Into<isize>,
+    V: Eq
+{
+    fn default() -> Grid3D<T, V> {
+        Grid3D::new()
+    }
 }
 
-impl<T> Path<T>
+impl<T, V> Extend<(Coordinate3D<T>, V)> for Grid3D<T, V>
 where
-    T: Copy
+    T: Ord + Copy + Hash
 {
-    pub fn new(location: &Coordinate3D<T>) -> Path<T> {
-        Path { locations: vec![location.clone()] }
+    fn extend<I: IntoIterator<Item = (Coordinate3D<T>, V)>>(&mut self, locations: I) {
+        for (location, value) in locations {
+            self.locations.insert(location, value);
+        }
     }
+}
 
-    pub fn from(path: &Path<T>) -> Path<T> {
-        Path { locations: path.locations.clone() }
+impl<T, V> FromIterator<(Coordinate3D<T>, V)> for Grid3D<T, V>
+where
+    T: Ord + Copy + Hash + From<isize> + Into<isize>,
+    V: Eq
+{
+    fn from_iter<I: IntoIterator<Item = (Coordinate3D<T>, V)>>(locations: I) -> Grid3D<T, V> {
+        let mut grid = Grid3D::new();
+        grid.extend(locations);
+        grid
     }
 }
 
-/// A Path target location or value
-pub enum PathTarget<T, V> {
-    Location(Coordinate3D<T>),
-    Value(V)
+impl<T, V> IntoIterator for Grid3D<T, V> {
+    type Item = (Coordinate3D<T>, V);
+    type IntoIter = hash_map::IntoIter<Coordinate3D<T>, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.locations.into_iter()
+    }
 }
 
-/// Find first shortest path found between start location and path target
-pub fn find_shortest_path<T, V>(
-    map: &Grid3D<T, V>,
-    start: &Coordinate3D<T>,
-    goal: &PathTarget<T, V>,
-    location_validator: Option<Box<dyn Fn(&V, &V) -> bool>>
-) -> Option<Path<T>>
-where
-    T: Ord + Copy + Hash + From<isize> + Into<isize> + Add<isize, Output = T> + Sub<isize, Output = T>,
-    V: Eq
-{
-    let mut visited_locations: HashSet<Coordinate3D<T>> = HashSet::new();
-    let mut paths_to_explored: Vec<Path<T>> = vec![Path::new(start)];
-
-    while paths_to_explored.len() > 0 {
-        let current_paths: Vec<Path<T>> = paths_to_explored.drain(..).collect();
-
-        for path in current_paths {
-            let current_location = path.locations.last().unwrap();
-            let current_value = map.get_value(&current_location).unwrap();
-
-            let adjacent_locations = get_adjacent_orthogonal_locations(&current_location);
-            let next_locations: Vec<Coordinate3D<T>> = adjacent_locations
-                .into_iter()
-                // Removing locations outside map
-                .filter(|l| map.get_value(&l) != None)
-                // Removing already visited locations
-                .filter(|l| visited_locations.contains(&l) == false)
-                .filter(|l| match &location_validator {
-                    // Removing invalid locations
-                    Some(validator) => (validator)(current_value, map.get_value(&l).unwrap()),
-                    None => true
-                })
-                .collect();
-
-            for location in next_locations.into_iter() {
-                let mut next_path = Path::from(&path);
-                next_path.locations.push(location.clone());
-
-                if match goal {
-                    PathTarget::Location(goal_location) => &location == goal_location,
-                    PathTarget::Value(goal_value) => map.get_value(&location).unwrap() == goal_value
-                } {
-                    // Found path to goal
-                    return Some(next_path);
+/// A 3d path of locations
+pub type Path<T> = grid::Path<Coordinate3D<T>>;
+
+/// A 3d path target location or value
+pub type PathTarget<T, V> = grid::PathTarget<Coordinate3D<T>, V>;
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_snapshot;
+    use rand::Rng;
+    use std::collections::HashMap;
+
+    const NB_RANDOM_CASES: usize = 100;
+
+    fn random_location(rng: &mut impl Rng) -> Coordinate3D<isize> {
+        Coordinate3D { x: rng.gen_range(-100..100), y: rng.gen_range(-100..100), z: rng.gen_range(-100..100) }
+    }
+
+    /// Random map of open (`true`) and wall (`false`) locations, at random offset (so that its range does not always contain zero)
+    fn random_map(rng: &mut impl Rng, wall_ratio: f64) -> Grid3D<isize, bool> {
+        let origin = random_location(rng);
+        let (width, height, depth) = (rng.gen_range(1..8), rng.gen_range(1..8), rng.gen_range(1..8));
+
+        let mut map = Grid3D::new();
+        for z in origin.z..origin.z + depth {
+            for y in origin.y..origin.y + height {
+                for x in origin.x..origin.x + width {
+                    map.add_location(Coordinate3D { x, y, z }, !rng.gen_bool(wall_ratio));
                 }
+            }
+        }
+
+        map
+    }
+
+    fn random_open_location(rng: &mut impl Rng, map: &Grid3D<isize, bool>) -> Option<Coordinate3D<isize>> {
+        let locations = map.get_locations_with_value(&true);
+        match locations.is_empty() {
+            true => None,
+            false => Some(locations[rng.gen_range(0..locations.len())])
+        }
+    }
+
+    #[test]
+    fn test_manhattan_distance() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..NB_RANDOM_CASES {
+            let (a, b, c) = (random_location(&mut rng), random_location(&mut rng), random_location(&mut rng));
+
+            assert_eq!(0, a.manhattan_distance(&a));
+            assert_eq!(a.manhattan_distance(&b), b.manhattan_distance(&a));
+            assert!(a.manhattan_distance(&c) <= a.manhattan_distance(&b) + b.manhattan_distance(&c));
+            for location in get_adjacent_orthogonal_locations(&a) {
+                assert_eq!(1, a.manhattan_distance(&location));
+            }
+        }
+    }
 
-                visited_locations.insert(location);
-                paths_to_explored.push(next_path);
+    #[test]
+    fn test_width_height_depth() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..NB_RANDOM_CASES {
+            let map = random_map(&mut rng, 0.0);
+
+            assert_eq!(map.max_x() - map.min_x() + 1, map.width());
+            assert_eq!(map.max_y() - map.min_y() + 1, map.height());
+            assert_eq!(map.max_z() - map.min_z() + 1, map.depth());
+            assert_eq!(map.size(), (map.width() * map.height() * map.depth()) as usize);
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..NB_RANDOM_CASES {
+            let mut grid: Grid3D<isize, bool> = Grid3D::new();
+            assert_eq!(None, grid.bounds());
+            assert_eq!((0, 0, 0), (grid.width(), grid.height(), grid.depth()));
+
+            // Bounds kept up to date as locations are added, then removed
+            for _ in 0..rng.gen_range(1..20) {
+                grid.add_location(random_location(&mut rng), rng.gen_bool(0.5));
+                assert_eq!(Cuboid::from_locations(grid.iter().map(|(location, _)| location)), grid.bounds());
             }
+            grid.keep_only_matching_locations(&|_, value| *value);
+            assert_eq!(Cuboid::from_locations(grid.iter().map(|(location, _)| location)), grid.bounds());
+            grid.add_location(random_location(&mut rng), true);
+            assert_eq!(Cuboid::from_locations(grid.iter().map(|(location, _)| location)), grid.bounds());
+
+            let bounds = grid.bounds().unwrap();
+            assert!(grid.locations().all(|(location, _)| bounds.contains(&location)));
+            assert_eq!((bounds.width(), bounds.height(), bounds.depth()), (grid.width(), grid.height(), grid.depth()));
+        }
+
+        assert_eq!("", Grid3D::<isize, bool>::new().draw(&false, &|_| "#"));
+    }
+
+    #[test]
+    fn test_map_operations() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..NB_RANDOM_CASES {
+            // Same content and bounds as hash map, whatever the operations
+            let mut locations: HashMap<Coordinate3D<isize>, usize> =
+                (0..rng.gen_range(1..20)).map(|_| (random_location(&mut rng), rng.gen_range(0..5))).collect();
+            let mut grid: Grid3D<isize, usize> = locations.clone().into_iter().collect();
+
+            let location = random_location(&mut rng);
+            *locations.entry(location).or_default() += 1;
+            *grid.entry(location).or_default() += 1;
+
+            let location = *locations.keys().next().unwrap();
+            assert_eq!(locations.remove(&location), grid.remove_location(&location));
+
+            locations.iter_mut().for_each(|(_, value)| *value *= 2);
+            grid.iter_mut().for_each(|(_, value)| *value *= 2);
+            locations.retain(|_, value| *value != 4);
+            grid.retain(|_, value| *value != 4);
+            grid.extend([(location, 1)]);
+            locations.extend([(location, 1)]);
+
+            assert_eq!(locations.len(), grid.size());
+            assert!(grid.iter().all(|(location, value)| locations.get(location) == Some(value)));
+            assert_eq!(Cuboid::from_locations(locations.keys()), grid.bounds());
         }
     }
 
-    None
+    #[test]
+    fn test_find_shortest_path() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..NB_RANDOM_CASES {
+            let wall_ratio = if rng.gen_bool(0.5) {
+                0.0
+            }
+            else {
+                0.3
+            };
+            let map = random_map(&mut rng, wall_ratio);
+            let (start, goal) = match (random_open_location(&mut rng, &map), random_open_location(&mut rng, &map)) {
+                (Some(start), Some(goal)) if start != goal => (start, goal),
+                _ => continue
+            };
+
+            let path = find_shortest_path(&map, &start, &PathTarget::Location(goal), Some(Box::new(|_, next| *next)));
+            let path = match (path, wall_ratio == 0.0) {
+                (Some(path), _) => path,
+                (None, false) => continue, // Goal may be walled off
+                (None, true) => panic!("No path found from {:?} to {:?} in open map", start, goal)
+            };
+
+            assert_eq!(Some(&start), path.locations.first());
+            assert_eq!(Some(&goal), path.locations.last());
+            for pair in path.locations.windows(2) {
+                assert_eq!(1, pair[0].manhattan_distance(&pair[1]));
+                assert_eq!(Some(&true), map.get_value(&pair[1]));
+            }
+
+            // Path length never beats manhattan distance, and matches it in open map
+            let nb_steps = (path.locations.len() - 1) as isize;
+            assert!(nb_steps >= start.manhattan_distance(&goal));
+            if wall_ratio == 0.0 {
+                assert_eq!(start.manhattan_distance(&goal), nb_steps);
+            }
+        }
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut grid: Grid3D<isize, bool> = Grid3D::new();
+        for (x, y, z) in [(0, 0, 0), (1, 0, 0), (1, 1, 0), (-1, 0, 1), (1, 1, 1)] {
+            grid.add_location(Coordinate3D { x, y, z }, true);
+        }
+
+        assert_snapshot!(
+            "grid",
+            grid.draw(&false, &|v| if *v {
+                "#"
+            }
+            else {
+                "."
+            })
+        );
+    }
 }
@@ -0,0 +1,93 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use crate::answer::SolveError;
+
+/// Solution parameters (e.g: `--param y=10`), overriding default values declared by each day
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    values: HashMap<String, String>,
+    used_keys: RefCell<HashSet<String>>
+}
+
+impl Params {
+    /// Constructor (no parameter set, so days use their default values)
+    pub fn new() -> Params {
+        Params::default()
+    }
+
+    /// Set parameter value, builder style (e.g: `Params::new().with("y", 10)` in tests)
+    pub fn with(mut self, key: &str, value: impl ToString) -> Params {
+        self.set(key, value);
+        self
+    }
+
+    /// Set parameter value
+    pub fn set(&mut self, key: &str, value: impl ToString) {
+        self.values.insert(String::from(key), value.to_string());
+    }
+
+    /// Set parameter from `key=value` text
+    pub fn set_from_str(&mut self, param: &str) -> Result<(), String> {
+        match param.split_once('=') {
+            Some((key, value)) if !key.is_empty() => {
+                self.set(key, value);
+                Ok(())
+            },
+            _ => Err(format!("Invalid parameter \"{param}\" (expected key=value)"))
+        }
+    }
+
+    /// Whether no parameter was set
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Get parameter value, or given default value when not set
+    pub fn get<T: FromStr>(&self, key: &str, default: T) -> Result<T, SolveError> {
+        self.used_keys.borrow_mut().insert(String::from(key));
+
+        match self.values.get(key) {
+            Some(value) => value.parse::<T>().map_err(|_| SolveError::InvalidParam(format!("Invalid value \"{value}\" for parameter \"{key}\""))),
+            None => Ok(default)
+        }
+    }
+
+    /// Parameters set but never read by solution (e.g: misspelled key)
+    pub fn unused_keys(&self) -> Vec<String> {
+        let used_keys = self.used_keys.borrow();
+        let mut keys: Vec<String> = self.values.keys().filter(|key| !used_keys.contains(*key)).cloned().collect();
+        keys.sort();
+        keys
+    }
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        let mut params = Params::new().with("y", 10);
+        params.set_from_str("key=811589153").unwrap();
+
+        assert_eq!(Ok(10), params.get::<isize>("y", 2000000));
+        assert_eq!(Ok(811589153), params.get::<isize>("key", 1));
+        assert_eq!(Ok(4000000), params.get::<isize>("size", 4000000));
+        assert!(params.get::<usize>("key", 1).is_ok());
+        assert!(Params::new().with("y", "abc").get::<isize>("y", 0).is_err());
+        assert!(params.set_from_str("=10").is_err());
+        assert!(params.set_from_str("y").is_err());
+    }
+
+    #[test]
+    fn test_unused_keys() {
+        let params = Params::new().with("y", 10).with("typo", 1);
+        params.get::<isize>("y", 0).unwrap();
+
+        assert_eq!(vec![String::from("typo")], params.unused_keys());
+    }
+}
@@ -0,0 +1,117 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Input parsing error, pointing at offending text (line and column numbers start at 1)
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub line_number: usize,
+    pub column: usize,
+    pub length: usize,
+    pub line: String,
+    pub message: String
+}
+
+impl ParseError {
+    /// Constructor
+    pub fn new(line_idx: usize, line: &str, column_idx: usize, length: usize, message: &str) -> ParseError {
+        ParseError { line_number: line_idx + 1, column: column_idx + 1, length: length.max(1), line: String::from(line), message: String::from(message) }
+    }
+
+    /// Error for whole line
+    pub fn at_line(line_idx: usize, line: &str, message: &str) -> ParseError {
+        ParseError::new(line_idx, line, 0, line.chars().count(), message)
+    }
+
+    /// Error for given text of line (e.g: a regex capture or a split token)
+    pub fn at_text(line_idx: usize, line: &str, text: &str, message: &str) -> ParseError {
+        ParseError::new(line_idx, line, text_column_idx(line, text), text.chars().count(), message)
+    }
+
+    /// Error at end of line (e.g: missing value)
+    pub fn at_end(line_idx: usize, line: &str, message: &str) -> ParseError {
+        ParseError::new(line_idx, line, line.chars().count(), 1, message)
+    }
+
+    /// Error for missing line after last one
+    pub fn missing_line(lines: &[&str], message: &str) -> ParseError {
+        match lines.last() {
+            Some(line) => ParseError::at_end(lines.len() - 1, line, message),
+            None => ParseError::new(0, "", 0, 1, message)
+        }
+    }
+
+    /// Error for something missing from whole input (pointing at its end, e.g: a map without start location)
+    pub fn at_end_of_input(data: &str, message: &str) -> ParseError {
+        ParseError::missing_line(&data.lines().collect::<Vec<&str>>(), message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let gutter = " ".repeat(self.line_number.to_string().len());
+        writeln!(fmt, "{} (line {}, column {})", self.message, self.line_number, self.column)?;
+        writeln!(fmt, "{gutter} |")?;
+        writeln!(fmt, "{} | {}", self.line_number, self.line)?;
+        write!(fmt, "{gutter} | {}{}", " ".repeat(self.column - 1), "^".repeat(self.length))
+    }
+}
+
+impl Error for ParseError {}
+
+/// Character index of text in line (text is expected to be a slice of line, otherwise first occurrence is used)
+fn text_column_idx(line: &str, text: &str) -> usize {
+    let line_start = line.as_ptr() as usize;
+    let text_start = text.as_ptr() as usize;
+    let byte_idx = match text_start >= line_start && text_start + text.len() <= line_start + line.len() {
+        true => text_start - line_start,
+        false => line.find(text).unwrap_or(0)
+    };
+
+    line[..byte_idx].chars().count()
+}
+
+/// Parse text of line to value, with error pointing at text if invalid
+pub fn parse_value<T: FromStr>(line_idx: usize, line: &str, text: &str) -> Result<T, ParseError> {
+    text.parse::<T>().map_err(|_| ParseError::at_text(line_idx, line, text, &format!("Invalid {} \"{text}\"", short_type_name::<T>())))
+}
+
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_value() {
+        let line = "addx 12";
+        assert_eq!(Ok(12), parse_value::<isize>(0, line, &line[5..]));
+
+        let line = "addx 1x2";
+        let error = parse_value::<isize>(2, line, &line[5..]).unwrap_err();
+        assert_eq!((3, 6, 3), (error.line_number, error.column, error.length));
+        assert_eq!("Invalid isize \"1x2\"", error.message);
+    }
+
+    #[test]
+    fn test_at_end_of_input() {
+        let error = ParseError::at_end_of_input("#..\n#.", "Missing start location");
+        assert_eq!((2, 3, 1, "#."), (error.line_number, error.column, error.length, &error.line[..]));
+
+        // Empty input
+        let error = ParseError::at_end_of_input("", "Missing start location");
+        assert_eq!((1, 1), (error.line_number, error.column));
+    }
+
+    #[test]
+    fn test_display() {
+        let line = "move 1 from x to 2";
+        let error = ParseError::at_text(9, line, "x", "Invalid stack");
+        assert_eq!("Invalid stack (line 10, column 13)\n   |\n10 | move 1 from x to 2\n   |             ^", error.to_string());
+    }
+}
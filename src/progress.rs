@@ -0,0 +1,152 @@
+//! Progress of long running loops (counter, rate and ETA), redrawn on a single terminal line when enabled by the runner
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Minimum duration between two redraws
+pub const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Show or hide progress (hidden by default, e.g: in tests or when running all solutions)
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether progress is currently shown
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Progress of a long running loop (e.g: `Boulder 1200/2022 (59.3%) - 1500/s - ETA 0.5s - height = 1820`)
+pub struct Progress {
+    label: String,
+    total: Option<u64>,
+    count: u64,
+    message: String,
+    start: Instant,
+    last_redraw: Option<Instant>
+}
+
+impl Progress {
+    /// Constructor, with total count when known (to compute ETA)
+    pub fn new(label: &str, total: Option<u64>) -> Progress {
+        Progress { label: String::from(label), total, count: 0, message: String::new(), start: Instant::now(), last_redraw: None }
+    }
+
+    /// Increment counter by one
+    pub fn inc(&mut self) {
+        self.set_count(self.count + 1);
+    }
+
+    /// Set counter value
+    pub fn set_count(&mut self, count: u64) {
+        self.count = count;
+        self.redraw(false);
+    }
+
+    /// Set message shown after counters (e.g: current best score)
+    pub fn set_message(&mut self, message: impl ToString) {
+        self.message = message.to_string();
+        self.redraw(false);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Counter increments per second
+    pub fn rate(&self) -> f64 {
+        let elapsed = self.start.elapsed().as_secs_f64();
+        match elapsed > 0.0 {
+            true => self.count as f64 / elapsed,
+            false => 0.0
+        }
+    }
+
+    /// Estimated remaining time, at current rate (when total is known)
+    pub fn eta(&self) -> Option<Duration> {
+        let total = self.total?;
+        let rate = self.rate();
+        match rate > 0.0 {
+            true => Some(Duration::from_secs_f64(total.saturating_sub(self.count) as f64 / rate)),
+            false => None
+        }
+    }
+
+    /// Progress status line
+    pub fn status(&self) -> String {
+        let mut parts = vec![match self.total {
+            Some(total) if total > 0 => format!("{} {}/{total} ({:.1}%)", self.label, self.count, 100.0 * self.count as f64 / total as f64),
+            _ => format!("{} {}", self.label, self.count)
+        }];
+
+        parts.push(format!("{:.0}/s", self.rate()));
+        if let Some(eta) = self.eta() {
+            parts.push(format!("ETA {:.1}s", eta.as_secs_f64()));
+        }
+        if !self.message.is_empty() {
+            parts.push(self.message.clone());
+        }
+
+        parts.join(" - ")
+    }
+
+    /// Draw final status and move on to next line (only if progress was shown)
+    pub fn finish(&mut self) {
+        if self.last_redraw.is_some() {
+            self.redraw(true);
+            eprintln!();
+            self.last_redraw = None;
+        }
+    }
+
+    fn redraw(&mut self, force: bool) {
+        if !enabled() {
+            return;
+        }
+
+        let now = Instant::now();
+        if !force && matches!(self.last_redraw, Some(last) if now.duration_since(last) < REDRAW_INTERVAL) {
+            return;
+        }
+        self.last_redraw = Some(now);
+
+        // Overwriting current terminal line
+        let mut stderr = io::stderr();
+        let _ = write!(stderr, "\r\x1b[2K{}", self.status());
+        let _ = stderr.flush();
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status() {
+        let mut progress = Progress::new("Round", Some(10));
+        for _ in 0..4 {
+            progress.inc();
+        }
+        progress.set_message("best = 42");
+
+        assert_eq!(4, progress.count());
+        assert!(progress.status().starts_with("Round 4/10 (40.0%) - "));
+        assert!(progress.status().contains(" - ETA "));
+        assert!(progress.status().ends_with(" - best = 42"));
+
+        let progress = Progress::new("Guess", None);
+        assert!(progress.status().starts_with("Guess 0 - "));
+        assert_eq!(None, progress.eta());
+    }
+}
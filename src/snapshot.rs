@@ -0,0 +1,136 @@
+//! Snapshot files of rendered outputs (e.g: grids or screens), compared by tests and reviewed with `cargo run -- snapshots`
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Snapshot file extension
+pub const SNAPSHOT_EXTENSION: &str = "snap";
+
+/// Extension of pending snapshot files, written by tests when output does not match snapshot
+pub const PENDING_EXTENSION: &str = "snap.new";
+
+/// Snapshots review action
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SnapshotAction {
+    Review,
+    Accept,
+    Reject
+}
+
+/// Assert rendered output matches snapshot named after calling file (e.g: `assert_snapshot!("screen", screen.draw())`)
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $actual:expr) => {
+        $crate::snapshot::assert_snapshot(&$crate::snapshot::snapshot_file(std::file!(), $name), &$actual)
+    };
+}
+
+/// Snapshot file of source file: next to day `test.txt` for solution modules (e.g: `day10/screen.snap`), in `snapshots` folder otherwise (e.g:
+/// `snapshots/navigation.grid.snap`)
+pub fn snapshot_file(source_file: &str, name: &str) -> PathBuf {
+    let source_file = Path::new(source_file);
+    let dir = source_file.parent().unwrap_or(Path::new(""));
+
+    match source_file.file_stem().and_then(|s| s.to_str()) {
+        Some("mod") | None => dir.join(format!("{name}.{SNAPSHOT_EXTENSION}")),
+        Some(module) => dir.join("snapshots").join(format!("{module}.{name}.{SNAPSHOT_EXTENSION}"))
+    }
+}
+
+/// Pending snapshot file written next to snapshot file (e.g: `screen.snap.new`)
+pub fn pending_file(snapshot_file: &Path) -> PathBuf {
+    snapshot_file.with_extension(PENDING_EXTENSION)
+}
+
+/// Compare output with snapshot file, writing pending snapshot to review on mismatch (panics, for tests)
+pub fn assert_snapshot(snapshot_file: &Path, actual: &str) {
+    let pending_file = pending_file(snapshot_file);
+    let expected = fs::read_to_string(snapshot_file).ok();
+
+    if expected.as_deref().map(|e| e.strip_suffix('\n').unwrap_or(e)) == Some(actual) {
+        let _ = fs::remove_file(&pending_file);
+        return;
+    }
+
+    if let Some(dir) = pending_file.parent() {
+        fs::create_dir_all(dir).unwrap();
+    }
+    fs::write(&pending_file, format!("{actual}\n")).unwrap();
+
+    match expected {
+        Some(expected) => panic!(
+            "Snapshot \"{}\" not matching (review with \"cargo run -- snapshots\", accept with \"cargo run -- snapshots accept\"):\n{}",
+            snapshot_file.display(),
+            diff_lines(expected.strip_suffix('\n').unwrap_or(&expected), actual)
+        ),
+        None => {
+            panic!("Missing snapshot \"{}\" (review with \"cargo run -- snapshots\", accept with \"cargo run -- snapshots accept\")", snapshot_file.display())
+        }
+    }
+}
+
+/// Line by line differences, with `-` for expected lines and `+` for actual lines
+pub fn diff_lines(expected: &str, actual: &str) -> String {
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+
+    let mut diff: Vec<String> = vec![];
+    for idx in 0..expected_lines.len().max(actual_lines.len()) {
+        match (expected_lines.get(idx), actual_lines.get(idx)) {
+            (Some(e), Some(a)) if e == a => diff.push(format!("  {e}")),
+            (e, a) => {
+                if let Some(e) = e {
+                    diff.push(format!("- {e}"));
+                }
+                if let Some(a) = a {
+                    diff.push(format!("+ {a}"));
+                }
+            }
+        }
+    }
+
+    diff.join("\n")
+}
+
+/// Pending snapshot files in given folder and its sub folders, ordered by path
+pub fn find_pending_snapshots(dir: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut files: Vec<PathBuf> = vec![];
+    for entry in fs::read_dir(dir).map_err(|err| format!("Failed to read \"{}\" dir: {err}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(find_pending_snapshots(&path)?);
+        }
+        else if path.to_string_lossy().ends_with(&format!(".{PENDING_EXTENSION}")) {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Snapshot file of pending snapshot file (e.g: `screen.snap` for `screen.snap.new`)
+pub fn accepted_file(pending_file: &Path) -> PathBuf {
+    pending_file.with_extension("")
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_file() {
+        assert_eq!(PathBuf::from("src/solutions/y2022/day10/screen.snap"), snapshot_file("src/solutions/y2022/day10/mod.rs", "screen"));
+        assert_eq!(PathBuf::from("src/snapshots/navigation.grid.snap"), snapshot_file("src/navigation.rs", "grid"));
+        assert_eq!(PathBuf::from("src/snapshots/navigation.grid.snap.new"), pending_file(&snapshot_file("src/navigation.rs", "grid")));
+        assert_eq!(PathBuf::from("src/snapshots/navigation.grid.snap"), accepted_file(Path::new("src/snapshots/navigation.grid.snap.new")));
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        assert_eq!("  #..#\n- ....\n+ .##.\n+ ####", diff_lines("#..#\n....", "#..#\n.##.\n####"));
+    }
+}
@@ -1,25 +1,103 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use std::time::Duration;
+
+/// A registered day solution part
+pub struct Solution {
+    pub year: u16,
+    pub day: &'static str,
+    pub part: u8,
+    pub run: fn(&str, &Params) -> Result<Answer, SolveError>,
+    /// Run timing input parsing and solving separately (for days registered with their input parser, for benchmarks)
+    pub timed_run: Option<TimedRun>
+}
+
+/// Solution run timing input parsing and solving separately
+pub type TimedRun = fn(&str, &Params) -> Result<TimedAnswer, SolveError>;
+
+/// Answer of a timed solution run
+pub struct TimedAnswer {
+    pub answer: Answer,
+    pub parse_duration: Duration,
+    pub solve_duration: Duration
+}
+
+/// Declare solution modules and register both of their parts, for the `YEAR` of calling module
+///
+/// A day registered with its input parser (e.g: `day14 => day14::read_cave_map`) must also expose `solve1` and `solve2` functions,
+/// solving each part from parsed input, so that benchmarks can time parsing and solving separately
+macro_rules! register_solutions {
+    ($($day:ident $(=> $parser:path)?),* $(,)?) => {
+        $(pub mod $day;)*
+
+        /// All registered solutions, ordered by (day, part)
+        pub const SOLUTIONS: &[$crate::solutions::Solution] = &[
+            $(
+                $crate::solutions::Solution { year: YEAR, day: stringify!($day), part: 1, run: $day::solution1, timed_run: register_solutions!(@timed_run $day::solve1 $(, $parser)?) },
+                $crate::solutions::Solution { year: YEAR, day: stringify!($day), part: 2, run: $day::solution2, timed_run: register_solutions!(@timed_run $day::solve2 $(, $parser)?) },
+            )*
+        ];
+    };
+    (@timed_run $solve:path) => {
+        None
+    };
+    (@timed_run $solve:path, $parser:path) => {
+        Some(|data, params| {
+            let start = std::time::Instant::now();
+            let input = std::hint::black_box($parser(data))?;
+            let parse_duration = start.elapsed();
+
+            let start = std::time::Instant::now();
+            let answer = $solve(input, params)?;
+
+            Ok($crate::solutions::TimedAnswer { answer, parse_duration, solve_duration: start.elapsed() })
+        })
+    };
+}
+
+/// Declare yearly solution modules (e.g: `y2022/mod.rs`, registering its days with `register_solutions!`)
+macro_rules! register_years {
+    ($($year:ident),* $(,)?) => {
+        $(pub mod $year;)*
+
+        /// Registered solutions of each year, ordered by year
+        pub const YEARS: &[(u16, &[Solution])] = &[$(($year::YEAR, $year::SOLUTIONS)),*];
+    };
+}
+
+register_years! {
+    y2022
+}
+
+/// All registered solutions (of given year, or of all years), ordered by (year, day, part)
+pub fn solutions(year: Option<u16>) -> impl Iterator<Item = &'static Solution> {
+    YEARS.iter().filter(move |(y, _)| year.unwrap_or(*y) == *y).flat_map(|(_, solutions)| solutions.iter())
+}
+
+/// Most recent registered year
+pub fn latest_year() -> u16 {
+    YEARS.iter().map(|(year, _)| *year).max().unwrap()
+}
+
+/// Find registered solution from year, day folder (e.g: `day01`) and part (e.g: `1` or `solution1`)
+pub fn find_solution(year: u16, day: &str, part: &str) -> Option<&'static Solution> {
+    let part = part.trim_start_matches("solution").parse::<u8>().ok()?;
+
+    solutions(Some(year)).find(|s| s.day == day && s.part == part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timed_run() {
+        let data = crate::read_file("src/solutions/y2022/day01/test.txt").unwrap();
+        let solution = find_solution(2022, "day01", "solution2").unwrap();
+        let timed_answer = (solution.timed_run.unwrap())(&data, &Params::new()).unwrap();
+        assert_eq!((solution.run)(&data, &Params::new()).unwrap(), timed_answer.answer);
+
+        // Days without registered parser can only be timed as a whole
+        assert!(find_solution(2022, "day02", "solution1").unwrap().timed_run.is_none());
+    }
+}
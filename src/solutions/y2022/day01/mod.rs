@@ -1,35 +1,38 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
+use crate::debug;
 use itertools::Itertools;
 
-pub fn solution1(data: String) -> i32 {
-    let values = get_values(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(get_values(data)?, params)
+}
+
+pub(crate) fn solve1(values: Vec<i32>, _params: &Params) -> Result<Answer, SolveError> {
     print_values(&values);
 
     let max_value = *values.iter().max().unwrap();
 
-    println!("=========================");
-    println!("Solution1: {max_value}");
-    println!("=========================");
+    Ok(Answer::from(max_value))
+}
 
-    max_value
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(get_values(data)?, params)
 }
 
-pub fn solution2(data: String) -> i32 {
-    let sorted_values = get_values(data).iter().sorted().map(|n| *n).collect();
+pub(crate) fn solve2(values: Vec<i32>, _params: &Params) -> Result<Answer, SolveError> {
+    let sorted_values = values.iter().sorted().map(|n| *n).collect();
     print_values(&sorted_values);
 
     let max_3_values_sum: i32 = sorted_values.iter().rev().take(3).sum();
 
-    println!("=========================");
-    println!("Solution2: {max_3_values_sum}");
-    println!("=========================");
-
-    max_3_values_sum
+    Ok(Answer::from(max_3_values_sum))
 }
 
 /////////////////////////////////////////////////
 
-fn get_values(data: String) -> Vec<i32> {
-    data.lines()
+pub(crate) fn get_values(data: &str) -> Result<Vec<i32>, ParseError> {
+    Ok(data.lines()
         .map(|n| n.parse::<i32>().unwrap_or(0))
         .coalesce(|l1, l2| {
             if l1 == 0 || l2 == 0 {
@@ -40,12 +43,12 @@ fn get_values(data: String) -> Vec<i32> {
             }
         })
         .filter(|n| *n > 0)
-        .collect()
+        .collect())
 }
 
 fn print_values(values: &Vec<i32>) {
     for (idx, val) in values.iter().enumerate() {
-        println!("{:02} --> {}", idx, val);
+        debug!("{:02} --> {}", idx, val);
     }
 }
 
@@ -64,12 +67,12 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(24000, solution1(data));
+        assert_eq!(24000, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(45000, solution2(data));
+        assert_eq!(45000, solution2(&data, &Params::new()).unwrap());
     }
 }
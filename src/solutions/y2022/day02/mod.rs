@@ -0,0 +1,142 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
+use itertools::Itertools;
+
+pub fn solution1(data: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let scores = get_scores(data)?;
+    let result: i32 = scores.iter().sum();
+
+    Ok(Answer::from(result))
+}
+
+pub fn solution2(data: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let scores = get_scores_v2(data)?;
+    let result: i32 = scores.iter().sum();
+
+    Ok(Answer::from(result))
+}
+
+/////////////////////////////////////////////////
+
+#[derive(Debug, Clone, Copy)]
+enum Move {
+    Rock = 1,
+    Paper = 2,
+    Scissor = 3
+}
+
+#[derive(Clone, Copy)]
+enum Outcome {
+    Loss = 0,
+    Draw = 3,
+    Win = 6
+}
+
+/// Both columns of a round line (e.g: "A Y")
+fn read_round(idx: usize, line: &str) -> Result<(&str, &str), ParseError> {
+    line.split_whitespace().collect_tuple().ok_or_else(|| ParseError::at_line(idx, line, "Invalid round (expected \"A X\")"))
+}
+
+fn char_to_move(idx: usize, line: &str, m: &str) -> Result<Move, ParseError> {
+    match m {
+        "A" | "X" => Ok(Move::Rock),
+        "B" | "Y" => Ok(Move::Paper),
+        "C" | "Z" => Ok(Move::Scissor),
+        _ => Err(ParseError::at_text(idx, line, m, &format!("Invalid move \"{m}\" (expected A, B, C, X, Y or Z)")))
+    }
+}
+
+fn get_round_outcome_value(m1: Move, m2: Move) -> i8 {
+    match (m2 as i8) - (m1 as i8) {
+        0 => Outcome::Draw as i8,
+        1 | -2 => Outcome::Win as i8,
+        _ => Outcome::Loss as i8
+    }
+}
+
+fn get_scores(data: &str) -> Result<Vec<i32>, ParseError> {
+    data.lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            let (m1, m2) = read_round(idx, line)?;
+            Ok((char_to_move(idx, line, m1)?, char_to_move(idx, line, m2)?))
+        })
+        .map_ok(|(m1, m2)| (m2 as i8) + get_round_outcome_value(m1, m2))
+        .map_ok(|score| score as i32)
+        .collect()
+}
+
+fn char_to_outcome(idx: usize, line: &str, m: &str) -> Result<Outcome, ParseError> {
+    match m {
+        "X" => Ok(Outcome::Loss),
+        "Y" => Ok(Outcome::Draw),
+        "Z" => Ok(Outcome::Win),
+        _ => Err(ParseError::at_text(idx, line, m, &format!("Invalid outcome \"{m}\" (expected X, Y or Z)")))
+    }
+}
+
+fn get_move_to_play(m1: Move, outcome: Outcome) -> Move {
+    match outcome {
+        Outcome::Loss => match m1 {
+            Move::Rock => Move::Scissor,
+            Move::Paper => Move::Rock,
+            Move::Scissor => Move::Paper
+        },
+        Outcome::Draw => m1,
+        Outcome::Win => match m1 {
+            Move::Rock => Move::Paper,
+            Move::Paper => Move::Scissor,
+            Move::Scissor => Move::Rock
+        }
+    }
+}
+
+fn get_scores_v2(data: &str) -> Result<Vec<i32>, ParseError> {
+    data.lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            let (m1, outcome) = read_round(idx, line)?;
+            Ok((char_to_move(idx, line, m1)?, char_to_outcome(idx, line, outcome)?))
+        })
+        .map_ok(|(m1, outcome)| (outcome as i8) + (get_move_to_play(m1, outcome) as i8))
+        .map_ok(|score| score as i32)
+        .collect()
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_test_file() -> String {
+        let current_file = std::file!();
+        let test_file = current_file.replace("mod.rs", "test.txt");
+        return crate::read_file(&test_file).unwrap();
+    }
+
+    #[test]
+    fn test_solution1() {
+        let data = read_test_file();
+        assert_eq!(15 + 7, solution1(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_solution2() {
+        let data = read_test_file();
+        assert_eq!(12 + 2, solution2(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let data = read_test_file().replace("B X", "B W");
+        let error = get_scores(&data).unwrap_err();
+        assert_eq!((2, 3, "Invalid move \"W\" (expected A, B, C, X, Y or Z)"), (error.line_number, error.column, &error.message[..]));
+        let error = get_scores_v2(&data).unwrap_err();
+        assert_eq!((2, 3, "Invalid outcome \"W\" (expected X, Y or Z)"), (error.line_number, error.column, &error.message[..]));
+
+        let error = get_scores("A Y\nB").unwrap_err();
+        assert_eq!((2, 1, "Invalid round (expected \"A X\")"), (error.line_number, error.column, &error.message[..]));
+    }
+}
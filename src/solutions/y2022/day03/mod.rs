@@ -1,9 +1,12 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
 use itertools::Itertools;
 use std::collections::HashSet;
 
-pub fn solution1(data: String) -> u32 {
-    let result = data
-        .lines()
+pub fn solution1(data: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let result: u32 = read_rucksacks(data)?
+        .into_iter()
         .map(|line| get_rucksack_misplaced_items(line))
         .map(|items| {
             // General case if there is more than one misplaced items
@@ -12,15 +15,14 @@ pub fn solution1(data: String) -> u32 {
         })
         .sum();
 
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
-pub fn solution2(data: String) -> u32 {
-    let lines: Vec<&str> = data.lines().collect();
+pub fn solution2(data: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let lines = read_rucksacks(data)?;
+    if lines.len() % 3 != 0 {
+        return Err(ParseError::missing_line(&lines, "Incomplete group of 3 rucksacks").into());
+    }
 
     let mut items: Vec<char> = vec![];
     for chunk in &lines.into_iter().chunks(3) {
@@ -32,20 +34,27 @@ pub fn solution2(data: String) -> u32 {
 
     let result: u32 = items.iter().map(|c| get_letter_value(c)).sum();
 
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
 
+/// Rucksack lines, checking that items are letters
+fn read_rucksacks(data: &str) -> Result<Vec<&str>, ParseError> {
+    data.lines()
+        .enumerate()
+        .map(|(idx, line)| match line.chars().position(|c| !c.is_ascii_alphabetic()) {
+            Some(column_idx) => Err(ParseError::new(idx, line, column_idx, 1, "Invalid item (expected a letter)")),
+            None => Ok(line)
+        })
+        .collect()
+}
+
+/// Priority of item letter (items are checked to be letters when read)
 fn get_letter_value(c: &char) -> u32 {
     match c {
-        'a'..='z' => (*c as u32) - 96,      // 1-26
-        'A'..='Z' => (*c as u32) - 64 + 26, // 27-52
-        _ => panic!("Invalid char {c}")
+        'a'..='z' => (*c as u32) - 96,  // 1-26
+        _ => (*c as u32) - 64 + 26      // 27-52
     }
 }
 
@@ -81,12 +90,22 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(157, solution1(data));
+        assert_eq!(157, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(70, solution2(data));
+        assert_eq!(70, solution2(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let data = read_test_file().replace("vJrw", "vJ1w");
+        let error = read_rucksacks(&data).unwrap_err();
+        assert_eq!((1, 3, "Invalid item (expected a letter)"), (error.line_number, error.column, &error.message[..]));
+
+        let data = read_test_file().lines().take(5).join("\n");
+        assert!(matches!(solution2(&data, &Params::new()), Err(SolveError::Parse(error)) if error.line_number == 5));
     }
 }
@@ -1,8 +1,10 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
 use regex::Regex;
 use std::collections::HashSet;
 
-pub fn solution1(data: String) -> i32 {
-    let result = data
+pub fn solution1(data: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let result: i32 = data
         .lines()
         .map(|line| get_ranges(&line))
         .map(|ranges| ranges_to_sets(ranges))
@@ -16,15 +18,11 @@ pub fn solution1(data: String) -> i32 {
         })
         .sum();
 
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
-pub fn solution2(data: String) -> i32 {
-    let result = data
+pub fn solution2(data: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let result: i32 = data
         .lines()
         .map(|line| get_ranges(&line))
         .map(|ranges| ranges_to_sets(ranges))
@@ -39,11 +37,7 @@ pub fn solution2(data: String) -> i32 {
         })
         .sum();
 
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
@@ -81,12 +75,12 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(2, solution1(data));
+        assert_eq!(2, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(4, solution2(data));
+        assert_eq!(4, solution2(&data, &Params::new()).unwrap());
     }
 }
@@ -0,0 +1,210 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::debug;
+use crate::parsing::{parse_value, ParseError};
+use itertools::Itertools;
+use regex::Regex;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_stacks_and_moves(data)?, params)
+}
+
+pub(crate) fn solve1((stacks, moves): (HashMap<i8, Stack>, Vec<Move>), _params: &Params) -> Result<Answer, SolveError> {
+    // Moves were checked when read (known stacks, with enough crates to move)
+    for m in moves {
+        for _n in 0..m.nb_crates {
+            let crate_name = stacks.get(&m.source_stack_id).unwrap().crates.borrow_mut().pop().unwrap();
+            stacks.get(&m.target_stack_id).unwrap().crates.borrow_mut().push(crate_name);
+        }
+    }
+
+    let result = read_top_crates(&stacks);
+
+    print_stacks(&stacks);
+
+    Ok(Answer::from(result))
+}
+
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_stacks_and_moves(data)?, params)
+}
+
+pub(crate) fn solve2((stacks, moves): (HashMap<i8, Stack>, Vec<Move>), _params: &Params) -> Result<Answer, SolveError> {
+    // Moves were checked when read (known stacks, with enough crates to move)
+    for m in moves {
+        let mut crates: Vec<char> = vec![];
+        for _n in 0..m.nb_crates {
+            let crate_name = stacks.get(&m.source_stack_id).unwrap().crates.borrow_mut().pop().unwrap();
+            crates.push(crate_name);
+        }
+        for c in crates.iter().rev() {
+            stacks.get(&m.target_stack_id).unwrap().crates.borrow_mut().push(*c);
+        }
+    }
+
+    let result = read_top_crates(&stacks);
+
+    print_stacks(&stacks);
+
+    Ok(Answer::from(result))
+}
+
+/////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub(crate) struct Stack {
+    crates: RefCell<Vec<char>>
+}
+
+#[derive(Debug)]
+pub(crate) struct Move {
+    nb_crates: i32,
+    source_stack_id: i8,
+    target_stack_id: i8
+}
+
+pub(crate) fn read_stacks_and_moves(data: &str) -> Result<(HashMap<i8, Stack>, Vec<Move>), ParseError> {
+    let lines: Vec<&str> = data.lines().collect();
+    let empty_line_idx = match lines.iter().position(|&l| l == "") {
+        Some(idx) if idx > 0 => idx,
+        Some(idx) => return Err(ParseError::at_line(idx, lines[idx], "Missing stacks before empty line")),
+        None => return Err(ParseError::missing_line(&lines, "Missing empty line between stacks and moves"))
+    };
+
+    // Reading stacks
+    let mut stacks: HashMap<i8, Stack> = HashMap::new();
+    let stack_ids_line = lines[empty_line_idx - 1];
+    for n in stack_ids_line.split_whitespace() {
+        let stack_id = parse_value::<i8>(empty_line_idx - 1, stack_ids_line, n)?;
+        stacks.insert(stack_id, Stack { crates: RefCell::new(vec![]) });
+    }
+    for idx in (0..empty_line_idx - 1).rev() {
+        let chars: Vec<char> = lines[idx].chars().collect();
+        let line_length = chars.len();
+        for n in 0..stacks.len() {
+            let crate_name_idx = 4 * n + 1;
+            if crate_name_idx < line_length && chars[crate_name_idx] != ' ' {
+                match stacks.get(&((n as i8) + 1)) {
+                    Some(stack) => stack.crates.borrow_mut().push(chars[crate_name_idx]),
+                    None => return Err(ParseError::new(idx, lines[idx], crate_name_idx, 1, &format!("Crate above unknown stack #{}", n + 1)))
+                }
+            }
+        }
+    }
+
+    // Reading moves (keeping track of stack sizes, so that moves never take more crates than stacked)
+    let mut stack_sizes: HashMap<i8, i32> = stacks.iter().map(|(stack_id, stack)| (*stack_id, stack.crates.borrow().len() as i32)).collect();
+    let mut moves: Vec<Move> = vec![];
+    let move_regex = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+    for idx in empty_line_idx + 1..lines.len() {
+        let line = lines[idx];
+        let captures = move_regex.captures(line).ok_or_else(|| ParseError::at_line(idx, line, "Invalid move (expected \"move N from X to Y\")"))?;
+        let m = Move {
+            nb_crates: parse_value::<i32>(idx, line, &captures[1])?,
+            source_stack_id: parse_value::<i8>(idx, line, &captures[2])?,
+            target_stack_id: parse_value::<i8>(idx, line, &captures[3])?
+        };
+        for (stack_id, text) in [(m.source_stack_id, &captures[2]), (m.target_stack_id, &captures[3])] {
+            if stacks.contains_key(&stack_id) == false {
+                return Err(ParseError::at_text(idx, line, text, &format!("Unknown stack \"{text}\"")));
+            }
+        }
+        if stack_sizes[&m.source_stack_id] < m.nb_crates {
+            let message = format!("Only {} crates in stack \"{}\"", stack_sizes[&m.source_stack_id], &captures[2]);
+            return Err(ParseError::at_text(idx, line, &captures[1], &message));
+        }
+        *stack_sizes.get_mut(&m.source_stack_id).unwrap() -= m.nb_crates;
+        *stack_sizes.get_mut(&m.target_stack_id).unwrap() += m.nb_crates;
+        moves.push(m);
+    }
+
+    Ok((stacks, moves))
+}
+
+/// Top crate of each stack, in stack ids order (skipping empty stacks)
+fn read_top_crates(stacks: &HashMap<i8, Stack>) -> String {
+    stacks.keys().sorted().filter_map(|stack_id| stacks[stack_id].crates.borrow().last().copied()).collect()
+}
+
+fn print_stacks(stacks: &HashMap<i8, Stack>) {
+    debug!();
+    debug!("{}", draw_stacks(stacks));
+}
+
+/// Stacks drawing, from top crates to stack ids line
+fn draw_stacks(stacks: &HashMap<i8, Stack>) -> String {
+    let mut lines: Vec<String> = vec![];
+    let max_nb_crates = stacks.values().map(|s| s.crates.borrow().len()).max().unwrap();
+
+    for i in 0..max_nb_crates {
+        let mut line = String::new();
+        for n in 0..stacks.len() {
+            let crates = stacks.get(&((n as i8) + 1)).unwrap().crates.borrow();
+            if i < crates.len() {
+                line.push_str(&format!("[{}] ", crates[i]));
+            }
+            else {
+                line.push_str("    ");
+            }
+        }
+        lines.push(String::from(line.trim_end()));
+    }
+
+    let separator = (0..stacks.len() * 4).map(|_| "-").collect::<String>();
+    let stack_ids_line = stacks.keys().sorted().map(|key| format!(" {}  ", key)).collect::<String>();
+
+    lines.reverse();
+    lines.push(separator);
+    lines.push(String::from(stack_ids_line.trim_end()));
+
+    lines.join("\n")
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_snapshot;
+
+    fn read_test_file() -> String {
+        let current_file = std::file!();
+        let test_file = current_file.replace("mod.rs", "test.txt");
+        return crate::read_file(&test_file).unwrap();
+    }
+
+    #[test]
+    fn test_solution1() {
+        let data = read_test_file();
+        assert_eq!("CMZ", solution1(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_solution2() {
+        let data = read_test_file();
+        assert_eq!("MCD", solution2(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let data = read_test_file().replace("move 2 from 2 to 1", "move 2 from 4 to 1");
+        let error = read_stacks_and_moves(&data).unwrap_err();
+        assert_eq!((8, 13, "Unknown stack \"4\""), (error.line_number, error.column, &error.message[..]));
+
+        let data = read_test_file().replace("move 1 from 2 to 1", "move 9 from 2 to 1");
+        let error = read_stacks_and_moves(&data).unwrap_err();
+        assert_eq!((6, 6, "Only 3 crates in stack \"2\""), (error.line_number, error.column, &error.message[..]));
+
+        // Emptied stacks have no top crate
+        let data = read_test_file().replace("move 1 from 1 to 2", "move 2 from 1 to 3");
+        assert_eq!("C", solution1(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_draw_stacks() {
+        let (stacks, _) = read_stacks_and_moves(&read_test_file()).unwrap();
+        assert_snapshot!("stacks", draw_stacks(&stacks));
+    }
+}
@@ -1,34 +1,28 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
 use std::collections::HashSet;
 
-pub fn solution1(data: String) -> usize {
+pub fn solution1(data: &str, _params: &Params) -> Result<Answer, SolveError> {
     let result = match find_marker_start(data, 4) {
         Some(result) => result,
-        None => panic!("No marker found!")
+        None => return Err(SolveError::NoSolution(String::from("No marker found")))
     };
 
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
-pub fn solution2(data: String) -> usize {
+pub fn solution2(data: &str, _params: &Params) -> Result<Answer, SolveError> {
     let result = match find_marker_start(data, 14) {
         Some(result) => result,
-        None => panic!("No marker found!")
+        None => return Err(SolveError::NoSolution(String::from("No marker found")))
     };
 
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
 
-fn find_marker_start(data: String, marker_size: usize) -> Option<usize> {
+fn find_marker_start(data: &str, marker_size: usize) -> Option<usize> {
     let buffer: Vec<&str> = data.split("").filter(|c| *c != "").collect();
     let buffer_len = buffer.len();
 
@@ -59,12 +53,17 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(7, solution1(data));
+        assert_eq!(7, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(19, solution2(data));
+        assert_eq!(19, solution2(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_no_marker() {
+        assert_eq!(Err(SolveError::NoSolution(String::from("No marker found"))), solution1("abcabc", &Params::new()));
     }
 }
@@ -1,54 +1,62 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
+use crate::debug;
 use itertools::Itertools;
 use std::{cell::RefCell, fmt, rc::Rc};
 
-pub fn solution1(data: String) -> i32 {
-    let filesystem = read_filesystem(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_filesystem(data)?, params)
+}
+
+pub(crate) fn solve1(filesystem: Rc<RefCell<Node>>, _params: &Params) -> Result<Answer, SolveError> {
     filesystem.borrow_mut().calculate_size();
     filesystem.as_ref().borrow().print_node(None);
 
     let mut dir_sizes = filesystem.as_ref().borrow().get_directories_size();
     dir_sizes = dir_sizes.into_iter().filter(|s| *s <= 100000).sorted().collect();
-    println!("{:?}", dir_sizes);
-
-    let result = dir_sizes.into_iter().sum();
+    debug!("{:?}", dir_sizes);
 
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
+    let result: i32 = dir_sizes.into_iter().sum();
 
-    result
+    Ok(Answer::from(result))
 }
 
 const TOTAL_DISK_SPACE: i32 = 70000000;
 const UPDATE_REQUIRED_SPACE: i32 = 30000000;
 
-pub fn solution2(data: String) -> i32 {
-    let filesystem = read_filesystem(data);
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_filesystem(data)?, params)
+}
+
+pub(crate) fn solve2(filesystem: Rc<RefCell<Node>>, params: &Params) -> Result<Answer, SolveError> {
+    let total_disk_space = params.get("total_disk_space", TOTAL_DISK_SPACE)?;
+    let update_required_space = params.get("update_required_space", UPDATE_REQUIRED_SPACE)?;
+
     filesystem.borrow_mut().calculate_size();
 
     let total_size = filesystem.as_ref().borrow().size;
-    let unused_size = TOTAL_DISK_SPACE - total_size;
+    let unused_size = total_disk_space - total_size;
 
     let mut result = 0;
-    if unused_size < UPDATE_REQUIRED_SPACE {
-        let required_size = UPDATE_REQUIRED_SPACE - unused_size;
+    if unused_size < update_required_space {
+        let required_size = update_required_space - unused_size;
         let mut dir_sizes = filesystem.as_ref().borrow().get_directories_size();
         dir_sizes = dir_sizes.into_iter().filter(|s| *s >= required_size).sorted().collect();
-        println!("{:?} > {}", &dir_sizes, required_size);
+        debug!("{:?} > {}", &dir_sizes, required_size);
 
-        result = dir_sizes.into_iter().min().expect("A solution must exists");
+        result = match dir_sizes.into_iter().min() {
+            Some(dir_size) => dir_size,
+            None => return Err(SolveError::NoSolution(format!("No directory big enough to free {required_size}")))
+        };
     }
 
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
 
-struct Node {
+pub(crate) struct Node {
     name: String,
     size: i32,
     files: Vec<Rc<RefCell<Node>>>, // Only for directories
@@ -93,7 +101,7 @@ impl Node {
             None => String::from("")
         };
 
-        println!("{}{}", &tabulation, &self);
+        debug!("{}{}", &tabulation, &self);
         for f in &self.files {
             f.as_ref().borrow().print_node(Some(depth.unwrap_or(0) + 1));
         }
@@ -126,7 +134,7 @@ impl Node {
     }
 }
 
-fn read_filesystem(data: String) -> Rc<RefCell<Node>> {
+pub(crate) fn read_filesystem(data: &str) -> Result<Rc<RefCell<Node>>, ParseError> {
     // Root directory
     let root_node = Rc::new(RefCell::new(Node::new("/", None, None)));
 
@@ -163,7 +171,7 @@ fn read_filesystem(data: String) -> Rc<RefCell<Node>> {
         current_dir.borrow_mut().insert_file(fname, fsize.parse::<i32>().unwrap(), Some(Rc::clone(&current_dir)));
     }
 
-    root_node
+    Ok(root_node)
 }
 
 /////////////////////////////////////////////////
@@ -181,12 +189,19 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(95437, solution1(data));
+        assert_eq!(95437, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(24933642, solution2(data));
+        assert_eq!(24933642, solution2(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_no_solution() {
+        let data = read_test_file();
+        let params = Params::new().with("update_required_space", 100000000);
+        assert_eq!(Err(SolveError::NoSolution(String::from("No directory big enough to free 78381165"))), solution2(&data, &params));
     }
 }
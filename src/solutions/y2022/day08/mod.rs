@@ -1,26 +1,27 @@
-use crate::navigation::Coordinate;
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
+use crate::navigation::{Coordinate, Grid, GridMap};
 use std::collections::HashMap;
 
-pub fn solution1(data: String) -> usize {
-    let trees_map = read_tree_map(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_tree_map(data)?, params)
+}
+
+pub(crate) fn solve1(trees_map: TreesMap, _params: &Params) -> Result<Answer, SolveError> {
     let result = trees_map.count_visible_trees();
 
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
+    Ok(Answer::from(result))
+}
 
-    result
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_tree_map(data)?, params)
 }
 
-pub fn solution2(data: String) -> i32 {
-    let trees_map = read_tree_map(data);
+pub(crate) fn solve2(trees_map: TreesMap, _params: &Params) -> Result<Answer, SolveError> {
     let result = trees_map.find_best_scenic_score();
 
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
@@ -101,7 +102,7 @@ impl Tree {
     }
 }
 
-struct TreesMap {
+pub(crate) struct TreesMap {
     height: usize,
     width: usize,
     trees: HashMap<Coordinate<usize>, Tree>
@@ -121,21 +122,21 @@ impl TreesMap {
     }
 }
 
-fn read_tree_map(data: String) -> TreesMap {
-    let mut trees: HashMap<Coordinate<usize>, Tree> = HashMap::new();
-
-    let mut height = 0;
-    let mut width = 0;
-    data.lines().enumerate().for_each(|(y, line)| {
-        height = y + 1;
-        width = line.len();
-        line.chars().enumerate().for_each(|(x, height)| {
-            let tree = Tree { location: Coordinate { x, y }, height: height.to_digit(10).unwrap() as u8 };
-            trees.insert(tree.location, tree);
+pub(crate) fn read_tree_map(data: &str) -> Result<TreesMap, ParseError> {
+    let heights_map: Grid<isize, u8> = Grid::try_parse(data, |height| match height.to_digit(10) {
+        Some(height) => Ok(Some(height as u8)),
+        None => Err(String::from("Invalid tree height (expected a digit)"))
+    })?;
+
+    let trees: HashMap<Coordinate<usize>, Tree> = heights_map
+        .iter()
+        .map(|(location, height)| {
+            let location = Coordinate { x: location.x as usize, y: location.y as usize };
+            (location, Tree { location, height: *height })
         })
-    });
+        .collect();
 
-    TreesMap { height, width, trees }
+    Ok(TreesMap { height: heights_map.height() as usize, width: heights_map.width() as usize, trees })
 }
 
 /////////////////////////////////////////////////
@@ -153,12 +154,19 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(21, solution1(data));
+        assert_eq!(21, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(8, solution2(data));
+        assert_eq!(8, solution2(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let data = read_test_file().replace("30373", "30x73");
+        let error = read_tree_map(&data).err().unwrap();
+        assert_eq!((1, 3, "Invalid tree height (expected a digit)"), (error.line_number, error.column, &error.message[..]));
     }
 }
@@ -1,9 +1,15 @@
-use crate::navigation::{Coordinate, Direction, Grid};
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::{parse_value, ParseError};
+use crate::grid::GridLike;
+use crate::navigation::{Coordinate, Direction, Grid, GridMap};
 use itertools::Itertools;
 
-pub fn solution1(data: String) -> usize {
-    let moves = read_moves(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_moves(data)?, params)
+}
 
+pub(crate) fn solve1(moves: Vec<Move>, _params: &Params) -> Result<Answer, SolveError> {
     let mut head_location = Coordinate { x: 0, y: 0 };
     let mut tail_location = Coordinate { x: 0, y: 0 };
     let mut tail_grid = Grid::new();
@@ -26,16 +32,14 @@ pub fn solution1(data: String) -> usize {
 
     let result = 1 + tail_grid.count_values(&Symbol::Visited);
 
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
-pub fn solution2(data: String) -> usize {
-    let moves = read_moves(data);
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_moves(data)?, params)
+}
 
+pub(crate) fn solve2(moves: Vec<Move>, _params: &Params) -> Result<Answer, SolveError> {
     let mut knot_locations: Vec<Coordinate<isize>> = (0..10).map(|_| Coordinate { x: 0, y: 0 }).collect();
     let mut tail_grid = Grid::new();
     tail_grid.add_location(Coordinate { x: 0, y: 0 }, Symbol::Start);
@@ -62,35 +66,32 @@ pub fn solution2(data: String) -> usize {
 
     let result = 1 + tail_grid.count_values(&Symbol::Visited);
 
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
 
-struct Move {
+pub(crate) struct Move {
     direction: Direction,
     nb_steps: i32
 }
 
-pub fn str_to_direction(d: &str) -> Direction {
+fn str_to_direction(idx: usize, line: &str, d: &str) -> Result<Direction, ParseError> {
     match d {
-        "U" => Direction::Up,
-        "R" => Direction::Right,
-        "D" => Direction::Down,
-        "L" => Direction::Left,
-        _ => panic!("Invalid direction {d}")
+        "U" => Ok(Direction::Up),
+        "R" => Ok(Direction::Right),
+        "D" => Ok(Direction::Down),
+        "L" => Ok(Direction::Left),
+        _ => Err(ParseError::at_text(idx, line, d, &format!("Invalid direction \"{d}\" (expected U, R, D or L)")))
     }
 }
 
-fn read_moves(data: String) -> Vec<Move> {
+pub(crate) fn read_moves(data: &str) -> Result<Vec<Move>, ParseError> {
     data.lines()
-        .map(|l| {
-            let (d, n) = l.split_whitespace().collect_tuple().unwrap();
-            return Move { direction: str_to_direction(d), nb_steps: n.parse::<i32>().unwrap() };
+        .enumerate()
+        .map(|(idx, l)| {
+            let (d, n) = l.split_whitespace().collect_tuple().ok_or_else(|| ParseError::at_line(idx, l, "Invalid move (expected \"D N\")"))?;
+            Ok(Move { direction: str_to_direction(idx, l, d)?, nb_steps: parse_value::<i32>(idx, l, n)? })
         })
         .collect()
 }
@@ -163,12 +164,23 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file("test.txt");
-        assert_eq!(13, solution1(data));
+        assert_eq!(13, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file("test2.txt");
-        assert_eq!(36, solution2(data));
+        assert_eq!(36, solution2(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let data = read_test_file("test.txt").replace("U 4", "X 4");
+        let error = read_moves(&data).err().unwrap();
+        assert_eq!((2, 1, "Invalid direction \"X\" (expected U, R, D or L)"), (error.line_number, error.column, &error.message[..]));
+
+        let data = read_test_file("test.txt").replace("L 3", "L three");
+        let error = read_moves(&data).err().unwrap();
+        assert_eq!((3, 3, "Invalid i32 \"three\""), (error.line_number, error.column, &error.message[..]));
     }
 }
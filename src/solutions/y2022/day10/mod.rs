@@ -1,35 +1,37 @@
-use crate::circuit::{read_instructions, ClockCircuit};
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::debug;
+use crate::circuit::{read_instructions, ClockCircuit, Instruction};
 
-pub fn solution1(data: String) -> isize {
-    let instructions = read_instructions(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_instructions(data)?, params)
+}
+
+pub(crate) fn solve1(instructions: Vec<Instruction>, _params: &Params) -> Result<Answer, SolveError> {
     let mut clock = ClockCircuit::new();
 
     for i in instructions {
         clock.run_instruction(&i);
     }
 
-    let result = [20, 60, 100, 140, 180, 220].into_iter().map(|n| clock.get_signal_strengh(Some(n))).sum();
+    let result: isize = [20, 60, 100, 140, 180, 220].into_iter().map(|n| clock.get_signal_strengh(Some(n))).sum();
 
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
+    Ok(Answer::from(result))
+}
 
-    result
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_instructions(data)?, params)
 }
 
-pub fn solution2(data: String) -> String {
-    let instructions = read_instructions(data);
+pub(crate) fn solve2(instructions: Vec<Instruction>, _params: &Params) -> Result<Answer, SolveError> {
     let mut clock = ClockCircuit::new();
     clock.run(&instructions);
 
     let screen = ClockCircuitScreen::from(&clock);
 
-    println!("=========================");
-    println!("Solution2: ");
     screen.show();
-    println!("=========================");
 
-    screen.value()
+    Ok(Answer::from(screen.value()))
 }
 
 /////////////////////////////////////////////////
@@ -84,13 +86,18 @@ impl ClockCircuitScreen {
 
     /// Print screen
     fn show(&self) {
+        debug!("{}", self.draw());
+    }
+
+    /// Framed screen drawing
+    fn draw(&self) -> String {
         let separator = (0..42).map(|_| "-").collect::<String>();
 
-        println!("{separator}");
-        for l in &self.lines {
-            println!("|{l}|");
-        }
-        println!("{separator}");
+        let mut lines = vec![separator.clone()];
+        lines.extend(self.lines.iter().map(|l| format!("|{l}|")));
+        lines.push(separator);
+
+        lines.join("\n")
     }
 }
 
@@ -99,6 +106,7 @@ impl ClockCircuitScreen {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::assert_snapshot;
 
     fn read_test_file() -> String {
         let current_file = std::file!();
@@ -109,7 +117,7 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(13140, solution1(data));
+        assert_eq!(13140, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
@@ -126,6 +134,20 @@ mod tests {
         ]
         .join("\n");
 
-        assert_eq!(expected, solution2(data), "Screen display not matching expected !");
+        assert_eq!(expected, solution2(&data, &Params::new()).unwrap(), "Screen display not matching expected !");
+    }
+
+    #[test]
+    fn test_draw_screen() {
+        let mut clock = ClockCircuit::new();
+        clock.run(&read_instructions(&read_test_file()).unwrap());
+        assert_snapshot!("screen", ClockCircuitScreen::from(&clock).draw());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let data = read_test_file().replacen("addx 15", "addx x5", 1);
+        let error = read_instructions(&data).unwrap_err();
+        assert_eq!((1, 6, "Invalid isize \"x5\""), (error.line_number, error.column, &error.message[..]));
     }
 }
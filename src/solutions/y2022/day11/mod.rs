@@ -1,39 +1,41 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::{parse_value, ParseError};
+use crate::{debug, info};
 use itertools::Itertools;
 
-pub fn solution1(data: String) -> i64 {
-    let mut monkeys = read_monkey_data(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_monkey_data(data)?, params)
+}
+
+pub(crate) fn solve1(mut monkeys: Vec<Monkey>, _params: &Params) -> Result<Answer, SolveError> {
     play_monkey_rounds(20, &mut monkeys, WorryReducingMethod::Factor(3), true);
 
     // Computing monkey business value
     let nb_inspects: Vec<i64> = monkeys.iter().map(|m| m.nb_inspects).collect();
-    let result = nb_inspects.into_iter().sorted().rev().take(2).product();
+    let result: i64 = nb_inspects.into_iter().sorted().rev().take(2).product();
 
     print_monkey_business(&monkeys);
 
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
+    Ok(Answer::from(result))
+}
 
-    result
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_monkey_data(data)?, params)
 }
 
-pub fn solution2(data: String) -> i64 {
-    let mut monkeys = read_monkey_data(data);
+pub(crate) fn solve2(mut monkeys: Vec<Monkey>, _params: &Params) -> Result<Answer, SolveError> {
     play_monkey_rounds(10000, &mut monkeys, WorryReducingMethod::Modulo, false);
 
     print_monkey_items(&monkeys);
 
     // Computing monkey business value
     let nb_inspects: Vec<i64> = monkeys.iter().map(|m| m.nb_inspects).collect();
-    let result = nb_inspects.into_iter().sorted().rev().take(2).product();
+    let result: i64 = nb_inspects.into_iter().sorted().rev().take(2).product();
 
     print_monkey_business(&monkeys);
 
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
@@ -43,7 +45,7 @@ struct Item {
     worry_level: i64
 }
 
-struct Monkey {
+pub(crate) struct Monkey {
     items: Vec<Item>,
     operation: Box<dyn Fn(i64) -> i64>,
     test: Box<dyn Fn(i64) -> usize>,
@@ -56,39 +58,47 @@ enum WorryReducingMethod {
     Factor(i64)
 }
 
-fn read_monkey_data(data: String) -> Vec<Monkey> {
+pub(crate) fn read_monkey_data(data: &str) -> Result<Vec<Monkey>, ParseError> {
     let mut monkeys = vec![];
 
-    let mut lines_iter = data.lines().filter(|l| l.trim() != "");
-    while let Some(line) = lines_iter.next() {
+    let lines: Vec<&str> = data.lines().collect();
+    let mut lines_iter = lines.iter().copied().enumerate().filter(|(_, l)| l.trim() != "");
+    while let Some((_, line)) = lines_iter.next() {
         if line.starts_with("Monkey") {
             // Starting items
-            let items =
-                lines_iter.next().unwrap().replace("  Starting items: ", "").split(", ").map(|l| Item { worry_level: l.parse::<i64>().unwrap() }).collect();
+            let (idx, line, value) = read_monkey_field(&mut lines_iter, &lines, "  Starting items: ")?;
+            let items = match value {
+                "" => vec![],
+                _ => value.split(", ").map(|l| Ok(Item { worry_level: parse_value::<i64>(idx, line, l)? })).collect::<Result<Vec<Item>, ParseError>>()?
+            };
 
             // Operation
-            let op_line = lines_iter.next().unwrap().replace("  Operation: new = old ", "");
-            let (op, value) = op_line.split(" ").collect_tuple().unwrap();
+            let (idx, line, value) = read_monkey_field(&mut lines_iter, &lines, "  Operation: new = old ")?;
+            let (op, value) = value.split(" ").collect_tuple().ok_or_else(|| ParseError::at_text(idx, line, value, "Invalid operation (expected \"<op> <value>\")"))?;
+            let invalid_operation = || ParseError::at_text(idx, line, op, &format!("Invalid operation \"{op}\""));
             let operation: Box<dyn Fn(i64) -> i64> = match value {
                 "old" => match op {
                     "+" => Box::new(move |old: i64| -> i64 { old + old }),
                     "*" => Box::new(move |old: i64| -> i64 { old * old }),
-                    _ => panic!("Invalid operation '{op}'")
+                    _ => return Err(invalid_operation())
                 },
                 _ => {
-                    let val = value.parse::<i64>().unwrap();
+                    let val = parse_value::<i64>(idx, line, value)?;
                     match op {
                         "+" => Box::new(move |old: i64| -> i64 { old + val }),
                         "*" => Box::new(move |old: i64| -> i64 { old * val }),
-                        _ => panic!("Invalid operation '{op}'")
+                        _ => return Err(invalid_operation())
                     }
                 }
             };
 
             // Test
-            let divisor = lines_iter.next().unwrap().replace("  Test: divisible by ", "").parse::<i64>().unwrap();
-            let m1_idx = lines_iter.next().unwrap().replace("    If true: throw to monkey ", "").parse::<usize>().unwrap();
-            let m2_idx = lines_iter.next().unwrap().replace("    If false: throw to monkey ", "").parse::<usize>().unwrap();
+            let (idx, line, value) = read_monkey_field(&mut lines_iter, &lines, "  Test: divisible by ")?;
+            let divisor = parse_value::<i64>(idx, line, value)?;
+            let (idx, line, value) = read_monkey_field(&mut lines_iter, &lines, "    If true: throw to monkey ")?;
+            let m1_idx = parse_value::<usize>(idx, line, value)?;
+            let (idx, line, value) = read_monkey_field(&mut lines_iter, &lines, "    If false: throw to monkey ")?;
+            let m2_idx = parse_value::<usize>(idx, line, value)?;
             let test_divisor = divisor.clone();
             let test = Box::new(move |current: i64| -> usize {
                 match current % divisor == 0 {
@@ -101,7 +111,20 @@ fn read_monkey_data(data: String) -> Vec<Monkey> {
         }
     }
 
-    monkeys
+    Ok(monkeys)
+}
+
+/// Read next monkey data line, returning line index, line and value following expected prefix
+fn read_monkey_field<'a>(
+    lines_iter: &mut impl Iterator<Item = (usize, &'a str)>,
+    lines: &[&'a str],
+    prefix: &str
+) -> Result<(usize, &'a str, &'a str), ParseError> {
+    let (idx, line) = lines_iter.next().ok_or_else(|| ParseError::missing_line(lines, &format!("Missing \"{}\" line", prefix.trim())))?;
+    match line.strip_prefix(prefix) {
+        Some(value) => Ok((idx, line, value)),
+        None => Err(ParseError::at_line(idx, line, &format!("Expected \"{}\" line", prefix.trim())))
+    }
 }
 
 fn play_monkey_rounds(nb_rounds: i64, monkeys: &mut Vec<Monkey>, manage_worry_level: WorryReducingMethod, show_monkey_items: bool) {
@@ -116,7 +139,7 @@ fn play_monkey_rounds(nb_rounds: i64, monkeys: &mut Vec<Monkey>, manage_worry_le
 
     for n in 0..nb_rounds {
         if show_monkey_items || (n + 1) % (nb_rounds / 10) == 0 {
-            println!("Playing round #{}...", n + 1);
+            info!("Playing round #{}...", n + 1);
         }
 
         for current_monkey_idx in 0..monkeys.len() {
@@ -149,16 +172,16 @@ fn play_monkey_rounds(nb_rounds: i64, monkeys: &mut Vec<Monkey>, manage_worry_le
 }
 
 fn print_monkey_items(monkeys: &Vec<Monkey>) {
-    println!("----------------------------------------");
+    debug!("----------------------------------------");
     for (idx, monkey) in monkeys.iter().enumerate() {
-        println!("Monkey {}: {:?}", idx + 1, monkey.items.iter().map(|i| i.worry_level).collect::<Vec<i64>>());
+        debug!("Monkey {}: {:?}", idx + 1, monkey.items.iter().map(|i| i.worry_level).collect::<Vec<i64>>());
     }
-    println!("----------------------------------------");
+    debug!("----------------------------------------");
 }
 
 fn print_monkey_business(monkeys: &Vec<Monkey>) {
     for (idx, monkey) in monkeys.iter().enumerate() {
-        println!("Monkey {} inspected items {} times.", idx + 1, monkey.nb_inspects);
+        debug!("Monkey {} inspected items {} times.", idx + 1, monkey.nb_inspects);
     }
 }
 
@@ -177,12 +200,19 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(10605, solution1(data));
+        assert_eq!(10605, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(2713310158, solution2(data));
+        assert_eq!(2713310158, solution2(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let data = read_test_file().replacen("new = old * 19", "new = old / 19", 1);
+        let error = read_monkey_data(&data).err().unwrap();
+        assert_eq!((3, 24, "Invalid operation \"/\""), (error.line_number, error.column, &error.message[..]));
     }
 }
@@ -1,50 +1,53 @@
-use crate::navigation::{find_shortest_path, Coordinate, Grid, PathTarget};
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
+use crate::info;
+use crate::grid::GridLike;
+use crate::navigation::{find_shortest_path, Coordinate, DenseGrid, GridMap, PathTarget};
+
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_elevation_map(data)?, params)
+}
 
-pub fn solution1(data: String) -> usize {
-    let (start, goal, elevation_map) = read_elevation_map(data);
+pub(crate) fn solve1((start, goal, elevation_map): ElevationMapData, _params: &Params) -> Result<Answer, SolveError> {
     elevation_map.print(&Symbol::Empty, &symbol_to_char);
-    println!("Start: {:?}", start);
-    println!("Goal: {:?}", goal);
+    info!("Start: {:?}", start);
+    info!("Goal: {:?}", goal);
 
     // Finding shortest path from start to goal
     let location_validator = build_location_validator(false);
     let path = match find_shortest_path(&elevation_map, &start, &PathTarget::Location(goal), Some(location_validator)) {
         Some(path) => path,
-        None => panic!("No path found !")
+        None => return Err(SolveError::NoSolution(format!("No path found from {:?} to {:?}", start, goal)))
     };
     let result = path.locations.len() - 1; // Excluding starting position
 
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
+    Ok(Answer::from(result))
+}
 
-    result
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_elevation_map(data)?, params)
 }
 
-pub fn solution2(data: String) -> usize {
-    let (_, goal, elevation_map) = read_elevation_map(data);
+pub(crate) fn solve2((_, goal, elevation_map): ElevationMapData, _params: &Params) -> Result<Answer, SolveError> {
     elevation_map.print(&Symbol::Empty, &symbol_to_char);
-    println!("Goal: {:?}", goal);
+    info!("Goal: {:?}", goal);
 
     // Finding reverse shortest path from goal to first location with lowest elevation value "a"
     let location_validator = build_location_validator(true);
     let reverse_path = match find_shortest_path(&elevation_map, &goal, &&PathTarget::Value(Symbol::Elevation("a".to_string())), Some(location_validator)) {
         Some(path) => path,
-        None => panic!("No path found !")
+        None => return Err(SolveError::NoSolution(format!("No path found from {:?} down to elevation \"a\"", goal)))
     };
     let result = reverse_path.locations.len() - 1; // Excluding starting position
 
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
 
 #[derive(Debug, Eq, PartialEq, Hash)]
-enum Symbol {
+pub(crate) enum Symbol {
     Start,
     Goal,
     Visited(String),   // Direction
@@ -62,31 +65,23 @@ fn symbol_to_char(symbol: &Symbol) -> &str {
     }
 }
 
-fn read_elevation_map(data: String) -> (Coordinate<isize>, Coordinate<isize>, Grid<isize, Symbol>) {
+/// Start and goal locations, with elevation map
+type ElevationMapData = (Coordinate<isize>, Coordinate<isize>, DenseGrid<isize, Symbol>);
+
+pub(crate) fn read_elevation_map(data: &str) -> Result<ElevationMapData, ParseError> {
     // TODO: usize instead ???
-    let mut start: Coordinate<isize> = Coordinate { x: 0, y: 0 };
-    let mut goal: Coordinate<isize> = Coordinate { x: 0, y: 0 };
-    let mut elevation_map = Grid::new();
-
-    data.lines().enumerate().for_each(|(y, row)| {
-        row.chars().enumerate().for_each(|(x, char)| {
-            let value = match char {
-                'S' => {
-                    start = Coordinate { x: x as isize, y: y as isize };
-                    Symbol::Start
-                },
-                'E' => {
-                    goal = Coordinate { x: x as isize, y: y as isize };
-                    Symbol::Goal
-                },
-                elevation => Symbol::Elevation(elevation.to_string())
-            };
-
-            elevation_map.add_location(Coordinate { x: x as isize, y: y as isize }, value);
+    let elevation_map = DenseGrid::parse(data, |char| {
+        Some(match char {
+            'S' => Symbol::Start,
+            'E' => Symbol::Goal,
+            elevation => Symbol::Elevation(elevation.to_string())
         })
     });
 
-    (start, goal, elevation_map)
+    let start = *elevation_map.get_mapped_locations_with_value(&Symbol::Start).first().ok_or_else(|| ParseError::at_end_of_input(data, "Missing start location \"S\""))?;
+    let goal = *elevation_map.get_mapped_locations_with_value(&Symbol::Goal).first().ok_or_else(|| ParseError::at_end_of_input(data, "Missing goal location \"E\""))?;
+
+    Ok((start, goal, elevation_map))
 }
 
 fn build_location_validator(is_reverse: bool) -> Box<dyn Fn(&Symbol, &Symbol) -> bool> {
@@ -128,12 +123,25 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(31, solution1(data));
+        assert_eq!(31, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(29, solution2(data));
+        assert_eq!(29, solution2(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let data = read_test_file().replace("S", "a");
+        let error = read_elevation_map(&data).err().unwrap();
+        assert_eq!((5, 9, "Missing start location \"S\""), (error.line_number, error.column, &error.message[..]));
+    }
+
+    #[test]
+    fn test_unreachable_goal() {
+        assert!(matches!(solution1("SzE", &Params::new()), Err(SolveError::NoSolution(_))));
+        assert!(matches!(solution2("SzE", &Params::new()), Err(SolveError::NoSolution(_))));
     }
 }
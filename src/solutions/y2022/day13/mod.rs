@@ -1,16 +1,18 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
+use crate::{info, trace};
 use itertools::Itertools;
 use serde::Deserialize;
 use std::{cmp::Ordering, fmt::Display};
 
-pub fn solution1(data: String) -> i32 {
-    let packets = read_packets(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_packets(data)?, params)
+}
 
-    #[cfg(test)]
-    {
-        // Debug logs...
-        for p in packets.iter() {
-            println!("Packet --> {}", p);
-        }
+pub(crate) fn solve1(packets: Vec<Packet>, _params: &Params) -> Result<Answer, SolveError> {
+    for p in packets.iter() {
+        trace!("Packet --> {}", p);
     }
 
     // Counting packets in correct order
@@ -26,20 +28,19 @@ pub fn solution1(data: String) -> i32 {
         chunk_idx += 1;
     }
 
-    let result = right_order_chunk_indices.iter().sum();
+    let result: i32 = right_order_chunk_indices.iter().sum();
 
-    println!("=========================");
-    println!("Indices: {:?}", right_order_chunk_indices);
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
+    info!("=========================");
+    info!("Indices: {:?}", right_order_chunk_indices);
 
-    result
+    Ok(Answer::from(result))
 }
 
-pub fn solution2(data: String) -> usize {
-    let mut packets = read_packets(data);
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_packets(data)?, params)
+}
 
+pub(crate) fn solve2(mut packets: Vec<Packet>, _params: &Params) -> Result<Answer, SolveError> {
     // Adding packet dividers
     packets.push(Packet { data: vec![PacketData::List(Box::new(vec![PacketData::Value(2)]))] });
     packets.push(Packet { data: vec![PacketData::List(Box::new(vec![PacketData::Value(6)]))] });
@@ -73,25 +74,18 @@ pub fn solution2(data: String) -> usize {
         .map(|(idx, _)| idx + 1)
         .collect::<Vec<usize>>();
 
-    let result = divider_indices.iter().product();
+    let result: usize = divider_indices.iter().product();
 
-    #[cfg(test)]
-    {
-        // Debug logs...
-        for p in sorted_packets.iter() {
-            println!("Packet --> {}", p);
-        }
+    for p in sorted_packets.iter() {
+        trace!("Packet --> {}", p);
     }
 
-    println!("=========================");
+    info!("=========================");
     for (idx, indice) in divider_indices.iter().enumerate() {
-        println!("Divider #{} at position {indice} --> {}", idx + 1, &sorted_packets[indice - 1]);
+        info!("Divider #{} at position {indice} --> {}", idx + 1, &sorted_packets[indice - 1]);
     }
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
 
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
@@ -115,7 +109,7 @@ impl Display for PacketData {
 }
 
 #[derive(Debug)]
-struct Packet {
+pub(crate) struct Packet {
     data: Vec<PacketData>
 }
 
@@ -186,14 +180,14 @@ impl PartialEq for Packet {
     }
 }
 
-fn read_packets(data: String) -> Vec<Packet> {
-    data.lines()
+pub(crate) fn read_packets(data: &str) -> Result<Vec<Packet>, ParseError> {
+    Ok(data.lines()
         .filter(|l| *l != "") // Removing empty lines
         .map(|line| {
             let data: Vec<PacketData> = serde_json::from_str(&line).unwrap();
             Packet { data }
         })
-        .collect()
+        .collect())
 }
 
 fn is_right_order_pair(left: &Vec<PacketData>, right: &Vec<PacketData>) -> Option<bool> {
@@ -258,12 +252,12 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(13 + 9, solution1(data));
+        assert_eq!(13 + 9, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!((10 + 2) * (14 + 4), solution2(data));
+        assert_eq!((10 + 2) * (14 + 4), solution2(&data, &Params::new()).unwrap());
     }
 }
@@ -1,24 +1,29 @@
-use crate::navigation::{Coordinate, Grid};
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
+use crate::grid::GridLike;
+use crate::navigation::{Coordinate, Grid, GridMap};
 use itertools::Itertools;
 
-pub fn solution1(data: String) -> usize {
-    let mut cave_map = read_cave_map(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_cave_map(data)?, params)
+}
+
+pub(crate) fn solve1(mut cave_map: Grid<isize, Symbol>, _params: &Params) -> Result<Answer, SolveError> {
     simulate_sand_flow(&mut cave_map, None);
 
     cave_map.print(&Symbol::Empty, &symbol_to_char);
 
     let result = cave_map.get_mapped_locations_with_value(&Symbol::Sand(false)).len();
 
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
-pub fn solution2(data: String) -> usize {
-    let mut cave_map = read_cave_map(data);
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_cave_map(data)?, params)
+}
 
+pub(crate) fn solve2(mut cave_map: Grid<isize, Symbol>, _params: &Params) -> Result<Answer, SolveError> {
     let y_floor = cave_map.max_y() + 2;
     simulate_sand_flow(&mut cave_map, Some(y_floor));
 
@@ -26,17 +31,13 @@ pub fn solution2(data: String) -> usize {
 
     let result = cave_map.get_mapped_locations_with_value(&Symbol::Sand(false)).len();
 
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
 
 #[derive(Debug, Eq, PartialEq, Hash)]
-enum Symbol {
+pub(crate) enum Symbol {
     Empty,
     Rock,
     Source,
@@ -53,7 +54,7 @@ fn symbol_to_char(symbol: &Symbol) -> &str {
     }
 }
 
-fn read_cave_map(data: String) -> Grid<isize, Symbol> {
+pub(crate) fn read_cave_map(data: &str) -> Result<Grid<isize, Symbol>, ParseError> {
     let mut cave_map: Grid<isize, Symbol> = Grid::new();
 
     // Adding sand source
@@ -100,7 +101,7 @@ fn read_cave_map(data: String) -> Grid<isize, Symbol> {
         }
     });
 
-    cave_map
+    Ok(cave_map)
 }
 
 fn simulate_sand_flow(cave_map: &mut Grid<isize, Symbol>, y_floor: Option<isize>) {
@@ -183,6 +184,8 @@ fn simulate_sand_flow(cave_map: &mut Grid<isize, Symbol>, y_floor: Option<isize>
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::assert_snapshot;
+    use crate::navigation::TextOptions;
 
     fn read_test_file() -> String {
         let current_file = std::file!();
@@ -193,12 +196,38 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(24, solution1(data));
+        assert_eq!(24, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(93, solution2(data));
+        assert_eq!(93, solution2(&data, &Params::new()).unwrap());
+    }
+
+    /// Inverse of `symbol_to_char`, empty locations not being part of cave map
+    fn char_to_symbol(char: char) -> Option<Symbol> {
+        match char {
+            '#' => Some(Symbol::Rock),
+            '+' => Some(Symbol::Source),
+            'o' => Some(Symbol::Sand(false)),
+            '~' => Some(Symbol::Sand(true)),
+            _ => None
+        }
+    }
+
+    #[test]
+    fn test_cave_text() {
+        let mut cave_map = read_cave_map(&read_test_file()).unwrap();
+        simulate_sand_flow(&mut cave_map, None);
+
+        let options = TextOptions { origin: cave_map.bounds().map(|b| b.min), blank: '.', ..TextOptions::default() };
+        let text = cave_map.render_with(&options, |symbol| symbol_to_char(symbol).chars().next().unwrap());
+        assert_snapshot!("cave", text);
+
+        // Same cave map once parsed back
+        let parsed_cave_map: Grid<isize, Symbol> = Grid::parse_with(&text, &options, char_to_symbol);
+        assert_eq!(cave_map.size(), parsed_cave_map.size());
+        assert!(cave_map.iter().all(|(location, symbol)| parsed_cave_map.get_value(location) == Some(symbol)));
     }
 }
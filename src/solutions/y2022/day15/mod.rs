@@ -1,81 +1,69 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
+use crate::{debug, info};
 use crate::navigation::Coordinate;
 use rand::seq::SliceRandom;
 use regex::Regex;
 use std::{
     cmp::{max, min},
-    collections::{HashMap, HashSet},
-    env
+    collections::{HashMap, HashSet}
 };
 
 const SOLUTION1_Y_VALUE: isize = 2000000;
 const SOLUTION2_Y_VALUE: isize = 4000000;
 
-const SOLUTION1_TEST_Y_VALUE: &str = "SOLUTION1_TEST_Y_VALUE";
-const SOLUTION2_TEST_Y_VALUE: &str = "SOLUTION2_TEST_Y_VALUE";
-
-pub fn solution1(data: String) -> usize {
-    let sensors = read_sensors_data(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_sensors_data(data)?, params)
+}
 
-    let y = match env::var(SOLUTION1_TEST_Y_VALUE) {
-        Ok(y) => y.parse::<isize>().unwrap(),
-        Err(_) => SOLUTION1_Y_VALUE
-    };
+pub(crate) fn solve1(sensors: Vec<Sensor>, params: &Params) -> Result<Answer, SolveError> {
+    let y = params.get("y", SOLUTION1_Y_VALUE)?;
 
     let searched_locations = search_row(sensors, y);
     let result = searched_locations.len();
 
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
-pub fn solution2(data: String) -> isize {
-    let sensors = read_sensors_data(data);
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_sensors_data(data)?, params)
+}
 
-    let size = match env::var(SOLUTION2_TEST_Y_VALUE) {
-        Ok(y) => y.parse::<isize>().unwrap(),
-        Err(_) => SOLUTION2_Y_VALUE
-    };
+pub(crate) fn solve2(sensors: Vec<Sensor>, params: &Params) -> Result<Answer, SolveError> {
+    let size = params.get("size", SOLUTION2_Y_VALUE)?;
     let boundary = Boundary { x_min: 0, y_min: 0, x_max: size, y_max: size };
 
-    println!("Mapping all searched locations in {:?}...", boundary);
-    println!("-------------------------");
+    info!("Mapping all searched locations in {:?}...", boundary);
+    info!("-------------------------");
     let searched_ranges = map_searched_row_ranges(&sensors, &boundary);
 
-    println!("=========================");
-    println!("Searching for single unsearched location...");
-    println!("-------------------------");
-    let result = match find_hidden_beacon(&searched_ranges, &boundary) {
-        Some(location) => {
-            println!("-------------------------");
-            println!("Location found at {:?}", location);
-            location.x * 4000000 + location.y
-        },
-        None => panic!("No solution found !")
-    };
-
-    println!("");
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
-
-    result
+    info!("=========================");
+    info!("Searching for single unsearched location...");
+    info!("-------------------------");
+    let location = find_hidden_beacon(&searched_ranges, &boundary)?;
+    info!("-------------------------");
+    info!("Location found at {:?}", location);
+    let result = location.x * 4000000 + location.y;
+
+    info!();
+
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
 
-struct Sensor {
+pub(crate) struct Sensor {
     location: Coordinate<isize>,
     beacon_location: Coordinate<isize>,
     beacon_distance: isize
 }
 
-fn read_sensors_data(data: String) -> Vec<Sensor> {
+pub(crate) fn read_sensors_data(data: &str) -> Result<Vec<Sensor>, ParseError> {
     let sensor_beacon_regex = Regex::new(r"^Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)$").unwrap();
 
-    data.lines()
+    Ok(data
+        .lines()
         .map(|line| {
             let captures = sensor_beacon_regex.captures(line).unwrap();
 
@@ -85,11 +73,11 @@ fn read_sensors_data(data: String) -> Vec<Sensor> {
 
             Sensor { location: sensor_location, beacon_location: beacon_location, beacon_distance: distance }
         })
-        .collect()
+        .collect())
 }
 
 fn print_sensor(sensor_idx: usize, sensor: &Sensor) {
-    println!(
+    debug!(
         "sensor #{:<2} ({:7}, {:7}) --> beacon ({:7}, {:7}) at distance |{:7}|",
         sensor_idx + 1,
         sensor.location.x,
@@ -190,7 +178,7 @@ fn map_searched_row_ranges(sensors: &Vec<Sensor>, boundary: &Boundary) -> HashMa
     searched_ranges
 }
 
-fn find_hidden_beacon(searched_ranges: &HashMap<isize, Vec<Range>>, boundary: &Boundary) -> Option<Coordinate<isize>> {
+fn find_hidden_beacon(searched_ranges: &HashMap<isize, Vec<Range>>, boundary: &Boundary) -> Result<Coordinate<isize>, SolveError> {
     let mut n = 0;
 
     // Randomizing rows in case we are lucky...
@@ -199,14 +187,17 @@ fn find_hidden_beacon(searched_ranges: &HashMap<isize, Vec<Range>>, boundary: &B
     y_values.shuffle(&mut rng);
 
     for y in y_values.into_iter() {
-        let ranges = searched_ranges.get(&y).expect("All row should be partially searched!");
+        let ranges = match searched_ranges.get(&y) {
+            Some(ranges) => ranges,
+            None => return Err(SolveError::NoSolution(format!("Row {y} is not searched by any sensor (no single unsearched location)")))
+        };
 
         // Searching row sorted searched range intervals to see if there is any gap...
         let mut x = 0;
         for r in ranges.iter() {
             if x < r.min {
                 // Found only possible unsearched location
-                return Some(Coordinate { x, y });
+                return Ok(Coordinate { x, y });
             }
 
             if x <= r.max {
@@ -217,11 +208,11 @@ fn find_hidden_beacon(searched_ranges: &HashMap<isize, Vec<Range>>, boundary: &B
 
         n += 1;
         if boundary.y_max < 100 || n % (boundary.y_max / 100) == 0 {
-            println!("Searching all rows at random ({:4.1}%)...", 100.0 * n as f32 / boundary.y_max as f32);
+            info!("Searching all rows at random ({:4.1}%)...", 100.0 * n as f32 / boundary.y_max as f32);
         }
     }
 
-    None
+    Err(SolveError::NoSolution(format!("No unsearched location in {:?}", boundary)))
 }
 
 /////////////////////////////////////////////////
@@ -239,18 +230,18 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-
-        env::set_var(SOLUTION1_TEST_Y_VALUE, "10");
-
-        assert_eq!(26, solution1(data));
+        assert_eq!(26, solution1(&data, &Params::new().with("y", 10)).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
+        assert_eq!(56000011, solution2(&data, &Params::new().with("size", 20)).unwrap());
+    }
 
-        env::set_var(SOLUTION2_TEST_Y_VALUE, "20");
-
-        assert_eq!(56000011, solution2(data));
+    #[test]
+    fn test_no_solution() {
+        let data = "Sensor at x=0, y=0: closest beacon is at x=1, y=0";
+        assert!(matches!(solution2(data, &Params::new().with("size", 5)), Err(SolveError::NoSolution(_))));
     }
 }
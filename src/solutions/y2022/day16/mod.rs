@@ -1,3 +1,8 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::{parse_value, ParseError};
+use crate::progress::Progress;
+use crate::{debug, info, trace};
 use itertools::Itertools;
 use regex::Regex;
 use std::{
@@ -9,58 +14,56 @@ use std::{
 // TODO: Add cache of visited state (start, remaining time, remaining valves) for both part1 & 2 to avoid re-calculating states all the time
 // TODO: Switch to depth first search instead and avoid exploring state which can't beat current best later ???
 
-pub fn solution1(data: String) -> usize {
-    let network = read_network_data(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_network_data(data)?, params)
+}
+
+pub(crate) fn solve1(network: ValveNetwork, _params: &Params) -> Result<Answer, SolveError> {
     network.print();
 
     let paths = map_all_shortest_paths(&network);
 
-    #[cfg(test)]
     for ((v1, v2), p) in paths.iter().sorted_by_key(|(key, _)| *key) {
-        println!("{} --> {} (time needed: {}) --> {:?}", v1, v2, p.weight, p.locations);
+        trace!("{} --> {} (time needed: {}) --> {:?}", v1, v2, p.weight, p.locations);
     }
 
     let path = find_optimal_path(&network, &paths, "AA", 30);
     let result = path.released_pressure;
 
-    println!("=========================");
-    println!("Path: {:?}", path.locations);
-    println!(
+    info!("=========================");
+    info!("Path: {:?}", path.locations);
+    info!(
         "Opened valves history: {:?}",
         path.opened_valves_history.iter().sorted_by_key(|(time, _)| -(**time as isize)).collect::<Vec<(&usize, &String)>>()
     );
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
 
-    result
+    Ok(Answer::from(result))
+}
+
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_network_data(data)?, params)
 }
 
-pub fn solution2(data: String) -> usize {
-    let network = read_network_data(data);
+pub(crate) fn solve2(network: ValveNetwork, _params: &Params) -> Result<Answer, SolveError> {
     let paths = map_all_shortest_paths(&network);
 
-    #[cfg(test)]
     for ((v1, v2), p) in paths.iter().sorted_by_key(|(key, _)| *key) {
-        println!("{} --> {} (time needed: {}) --> {:?}", v1, v2, p.weight, p.locations);
+        trace!("{} --> {} (time needed: {}) --> {:?}", v1, v2, p.weight, p.locations);
     }
 
     let paths = find_optimal_combined_paths(&network, &paths, "AA", 26, 2);
-    let result = paths.iter().map(|p| p.released_pressure).sum();
+    let result: usize = paths.iter().map(|p| p.released_pressure).sum();
 
-    println!("=========================");
+    info!("=========================");
     for path in paths.iter() {
-        println!("Subpath : {:?}", path.locations);
-        println!(
+        info!("Subpath : {:?}", path.locations);
+        info!(
             "Opened valves history: {:?}",
             path.opened_valves_history.iter().sorted_by_key(|(time, _)| -(**time as isize)).collect::<Vec<(&usize, &String)>>()
         );
     }
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
 
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
@@ -80,14 +83,14 @@ struct Valve {
 }
 
 #[derive(Debug)]
-struct ValveNetwork {
+pub(crate) struct ValveNetwork {
     valves: HashMap<String, Rc<RefCell<Valve>>>
 }
 
 impl ValveNetwork {
     fn print(&self) {
         for (_, v) in self.valves.iter().sorted_by_key(|(key, _)| *key) {
-            println!(
+            debug!(
                 "{} ({:<2}) --> {:?}",
                 v.as_ref().borrow().id,
                 v.as_ref().borrow().flow_rate,
@@ -97,14 +100,16 @@ impl ValveNetwork {
     }
 }
 
-fn read_network_data(data: String) -> ValveNetwork {
+pub(crate) fn read_network_data(data: &str) -> Result<ValveNetwork, ParseError> {
     let valve_regex = Regex::new(r"^Valve ([A-Z]{2}) has flow rate=(\d+); tunnels? leads? to valves? (([A-Z]{2},? ?)*)$").unwrap();
 
     let mut valves = HashMap::new();
-    data.lines().for_each(|line| {
-        let captures = valve_regex.captures(line).unwrap();
+    for (idx, line) in data.lines().enumerate() {
+        let captures = valve_regex
+            .captures(line)
+            .ok_or_else(|| ParseError::at_line(idx, line, "Invalid valve (expected \"Valve XX has flow rate=N; tunnels lead to valves YY, ZZ\")"))?;
         let id = String::from(&captures[1]);
-        let flow_rate = captures[2].parse::<usize>().unwrap();
+        let flow_rate = parse_value::<usize>(idx, line, &captures[2])?;
         let valve_ids: Vec<String> = captures[3].split(", ").map(|s| String::from(s)).collect();
 
         // Creating valve is it does not already exists
@@ -128,9 +133,9 @@ fn read_network_data(data: String) -> ValveNetwork {
             // Creating tunnel from valve to other valve
             valve.borrow_mut().tunnels.push(Tunnel { valve: Rc::clone(other_valve), time_to_traverse: 1 });
         }
-    });
+    }
 
-    ValveNetwork { valves }
+    Ok(ValveNetwork { valves })
 }
 
 #[derive(Debug, Clone)]
@@ -227,14 +232,18 @@ fn find_optimal_subset_path(
         released_pressure: 0
     }];
 
+    let mut progress = show_progress.then(|| Progress::new("Explored paths", None));
     while path_to_explored.len() > 0 {
-        if show_progress {
-            println!("-------------------------");
-            println!("Number of paths to explore: {}", path_to_explored.len());
+        if let Some(progress) = progress.as_mut() {
+            progress.set_message(format!("{} paths to explore", path_to_explored.len()));
         }
 
         let mut current_paths: Vec<GlobalPath> = path_to_explored.drain(..).collect();
         for path in current_paths.iter_mut() {
+            if let Some(progress) = progress.as_mut() {
+                progress.inc();
+            }
+
             // Current valve
             let current_valve = network.valves.get(path.locations.last().unwrap()).unwrap().as_ref().borrow();
             if current_valve.flow_rate > 0 {
@@ -280,9 +289,10 @@ fn find_optimal_subset_path(
         }
     }
 
-    if show_progress {
-        println!("-------------------------");
-        println!("Number of paths found: {}", found_paths.len());
+    if let Some(mut progress) = progress {
+        progress.finish();
+        info!("-------------------------");
+        info!("Number of paths found: {}", found_paths.len());
     }
 
     // Return optimal path (will always exists)
@@ -333,8 +343,10 @@ fn find_optimal_combined_paths(
     let mut nb_combinations = 0;
     let mut max_released_pressure = 0;
     let mut partial_solutions = vec![];
+    let mut progress = Progress::new("Combination", None);
     for (idx, combination) in subsets_combinations_iterator.enumerate() {
         nb_combinations += 1;
+        progress.inc();
 
         let mut partial_paths = vec![];
         for subset in combination.iter() {
@@ -345,15 +357,15 @@ fn find_optimal_combined_paths(
         let max_pressure = partial_paths.iter().map(|p| p.released_pressure).sum::<usize>();
         if max_pressure > max_released_pressure {
             max_released_pressure = max_pressure;
-            println!("-------------------------");
-            println!("Combination #{} --> {:?}", idx + 1, combination);
-            println!("Max pressure: {}", partial_paths.iter().map(|p| p.released_pressure).sum::<usize>());
+            progress.set_message(format!("max pressure = {max_pressure}"));
+            debug!("Combination #{} --> {:?}", idx + 1, combination);
         }
 
         partial_solutions.push(partial_paths);
     }
-    println!("-------------------------");
-    println!("Number of explored combinations: {}", nb_combinations);
+    progress.finish();
+    info!("-------------------------");
+    info!("Number of explored combinations: {}", nb_combinations);
 
     // Return optimal path (will always exists)
     partial_solutions.into_iter().max_by_key(|s| s.iter().map(|s| s.released_pressure).sum::<usize>()).unwrap()
@@ -374,12 +386,12 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(1651, solution1(data));
+        assert_eq!(1651, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(1707, solution2(data));
+        assert_eq!(1707, solution2(&data, &Params::new()).unwrap());
     }
 }
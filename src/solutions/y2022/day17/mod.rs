@@ -1,57 +1,72 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
+use crate::progress::Progress;
 use num::FromPrimitive;
 use num_derive::FromPrimitive;
 
+use crate::grid::GridLike;
 use crate::navigation::{Coordinate, Grid};
 
-pub fn solution1(data: String) -> usize {
-    let jet_patterns = read_jet_patterns(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_jet_patterns(data)?, params)
+}
+
+pub(crate) fn solve1(jet_patterns: Vec<JetPattern>, params: &Params) -> Result<Answer, SolveError> {
+    let nb_boulders = params.get("nb_boulders", 2022)?;
+
     let mut chamber = Chamber::new(jet_patterns);
-    chamber.simulate_boulders(2022);
+    chamber.simulate_boulders(nb_boulders);
 
     #[cfg(test)]
     {
+        use crate::navigation::GridMap;
         chamber.grid.print(&Symbol::Empty, &symbol_to_char);
     }
 
     let result = chamber.rock_tower_height;
 
-    println!("=========================");
-    println!("Solution1: {}", result);
-    println!("=========================");
+    Ok(Answer::from(result))
+}
 
-    result
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_jet_patterns(data)?, params)
 }
 
-pub fn solution2(data: String) -> usize {
-    let jet_patterns = read_jet_patterns(data);
+pub(crate) fn solve2(jet_patterns: Vec<JetPattern>, params: &Params) -> Result<Answer, SolveError> {
+    let nb_boulders = params.get("nb_boulders", 1000000000000)?;
+
     let mut chamber = Chamber::new(jet_patterns);
-    chamber.simulate_boulders(1000000000000);
+    chamber.simulate_boulders(nb_boulders);
 
     // TODO: find repeating pattern to skip ahead (1 trillion is too much...)
 
     let result = chamber.rock_tower_height;
 
-    println!("=========================");
-    println!("Solution2: {}", result);
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
 
 #[derive(Debug)]
-enum JetPattern {
+pub(crate) enum JetPattern {
     Left,
     Right
 }
 
-fn read_jet_patterns(data: String) -> Vec<JetPattern> {
-    data.chars().map(|c| match c {
-        '<' => JetPattern::Left,
-        '>' => JetPattern::Right,
-        _ => panic!("Invalid jet pattern")
-    }).collect()
+pub(crate) fn read_jet_patterns(data: &str) -> Result<Vec<JetPattern>, ParseError> {
+    let mut jet_patterns = vec![];
+    for (idx, line) in data.lines().enumerate() {
+        for (column_idx, c) in line.chars().enumerate() {
+            jet_patterns.push(match c {
+                '<' => JetPattern::Left,
+                '>' => JetPattern::Right,
+                _ => return Err(ParseError::new(idx, line, column_idx, 1, "Invalid jet pattern (expected \"<\" or \">\")"))
+            });
+        }
+    }
+
+    Ok(jet_patterns)
 }
 
 const CHAMBER_WIDTH: isize = 7;
@@ -219,6 +234,7 @@ impl Chamber {
         let mut j = 0;
         let mut n = 0;
 
+        let mut progress = Progress::new("Boulder", Some(nb_boulders as u64));
         while n < nb_boulders {
             // Next boulder
             let mut boulder = self.spawn_next_boulder();
@@ -250,8 +266,9 @@ impl Chamber {
             // Cleaning up old coordinate to keep memory usage low...
             self.grid.keep_only_matching_locations(&|c: &Coordinate<isize>, _| -> bool { self.rock_tower_height as isize - c.y.abs() < 100 });
 
-            if n % 1000000 == 0 {
-                println!("Boulder #{n} --> height = {} --> size {}", self.rock_tower_height, self.grid.size());
+            progress.set_count(n as u64);
+            if n % 1000 == 0 {
+                progress.set_message(format!("height = {} - size = {}", self.rock_tower_height, self.grid.size()));
             }
         }
 
@@ -273,12 +290,19 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(3068, solution1(data));
+        assert_eq!(3068, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(1514285714288, solution2(data));
+        assert_eq!(1514285714288, solution2(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let data = read_test_file().replacen("<", "x", 1);
+        let error = read_jet_patterns(&data).unwrap_err();
+        assert_eq!((1, 4, "Invalid jet pattern (expected \"<\" or \">\")"), (error.line_number, error.column, &error.message[..]));
     }
 }
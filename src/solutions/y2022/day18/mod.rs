@@ -1,35 +1,38 @@
-use std::collections::{HashMap, HashSet};
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
+use crate::info;
+use std::collections::HashSet;
 
-use crate::navigation3d::{find_shortest_path, get_adjacent_orthogonal_locations, Coordinate3D, Grid3D, PathTarget};
+use crate::grid::{flood_fill, GridLike};
+use crate::navigation3d::{get_adjacent_orthogonal_locations, Coordinate3D, Grid3D};
 
 use itertools::Itertools;
 
-pub fn solution1(data: String) -> usize {
-    let cubes = read_cubes(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_cubes(data)?, params)
+}
+
+pub(crate) fn solve1(cubes: HashSet<Coordinate3D<isize>>, _params: &Params) -> Result<Answer, SolveError> {
     let nb_faces = count_non_touching_faces(&cubes);
 
-    println!("=========================");
-    println!("Solution1: {nb_faces}");
-    println!("=========================");
+    Ok(Answer::from(nb_faces))
+}
 
-    nb_faces
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_cubes(data)?, params)
 }
 
-pub fn solution2(data: String) -> usize {
-    let cubes = read_cubes(data);
+pub(crate) fn solve2(cubes: HashSet<Coordinate3D<isize>>, _params: &Params) -> Result<Answer, SolveError> {
     let cubes_map = build_cubes_map(&cubes);
     let nb_faces = count_external_faces(&cubes, &cubes_map);
 
-    println!("=========================");
-    println!("Solution2: {nb_faces}");
-    println!("=========================");
-
-    nb_faces
+    Ok(Answer::from(nb_faces))
 }
 
 /////////////////////////////////////////////////
 
-fn read_cubes(data: String) -> HashSet<Coordinate3D<isize>> {
+pub(crate) fn read_cubes(data: &str) -> Result<HashSet<Coordinate3D<isize>>, ParseError> {
     let mut cubes = HashSet::new();
 
     data.lines().for_each(|line| {
@@ -37,7 +40,7 @@ fn read_cubes(data: String) -> HashSet<Coordinate3D<isize>> {
         cubes.insert(Coordinate3D { x, y, z });
     });
 
-    cubes
+    Ok(cubes)
 }
 
 fn count_non_touching_faces(cubes: &HashSet<Coordinate3D<isize>>) -> usize {
@@ -92,37 +95,13 @@ fn build_cubes_map(cubes: &HashSet<Coordinate3D<isize>>) -> Grid3D<isize, Symbol
 }
 
 fn count_external_faces(cubes: &HashSet<Coordinate3D<isize>>, cubes_map: &Grid3D<isize, Symbol>) -> usize {
-    let mut nb_faces = 0;
-    let mut adjacent_empty_spaces: HashMap<Coordinate3D<isize>, usize> = HashMap::new();
+    // Filling external space from any location on perimeter boundary (all connected to each other)
+    info!("Filling external space around {} cubes...", cubes.len());
+    let perimeter_location = cubes_map.get_mapped_locations_with_value(&Symbol::Perimeter)[0];
+    let external_locations = flood_fill(cubes_map, &perimeter_location, Some(Box::new(|_: &Symbol, next: &Symbol| *next != Symbol::Cube)));
 
-    // Counting non touching faces
-    println!("Mapping non touching faces...");
-    for c in cubes.iter() {
-        // Two unit cubes are touching in one direction if coordinate are adjacent
-        for c2 in get_adjacent_orthogonal_locations(&c).into_iter() {
-            if cubes.contains(&c2) == false {
-                nb_faces += 1;
-
-                // Keeping track of connected empty spaces to validate later
-                match adjacent_empty_spaces.get(&c2) {
-                    Some(v) => adjacent_empty_spaces.insert(c2, v + 1),
-                    None => adjacent_empty_spaces.insert(c2, 1)
-                };
-            }
-        }
-    }
-
-    // Validating connected empty spaces adjacent to non touching faces
-    println!("Validating {} empty spaces connected to {} faces...", adjacent_empty_spaces.len(), adjacent_empty_spaces.values().sum::<usize>());
-    let goal = PathTarget::Value(Symbol::Perimeter); // Any value on external perimeter boundary
-    for (c, nb_touching_faces) in adjacent_empty_spaces.iter() {
-        let location_validator: Box<dyn Fn(&Symbol, &Symbol) -> bool> = Box::new(|_: &Symbol, next: &Symbol| -> bool { *next != Symbol::Cube });
-        if find_shortest_path(cubes_map, &c, &goal, Some(location_validator)) == None {
-            nb_faces -= nb_touching_faces; // Uncounting all touching faces from before
-        }
-    }
-
-    nb_faces
+    // Counting faces touching external space
+    cubes.iter().map(|c| get_adjacent_orthogonal_locations(c).into_iter().filter(|c2| external_locations.contains(c2)).count()).sum()
 }
 
 /////////////////////////////////////////////////
@@ -140,12 +119,12 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(64, solution1(data));
+        assert_eq!(64, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(58, solution2(data));
+        assert_eq!(58, solution2(&data, &Params::new()).unwrap());
     }
 }
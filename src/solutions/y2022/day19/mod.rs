@@ -1,39 +1,42 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::{parse_value, ParseError};
+use crate::progress::Progress;
+use crate::{debug, info, trace};
 use std::{collections::HashMap, fmt};
 
 use regex::Regex;
 
-pub fn solution1(data: String) -> usize {
-    let mut blueprints = read_blueprints(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_blueprints(data)?, params)
+}
 
+pub(crate) fn solve1(mut blueprints: Vec<Blueprint>, _params: &Params) -> Result<Answer, SolveError> {
     for blueprint in blueprints.iter_mut() {
         blueprint.print();
         analyze_blueprint(blueprint, 24);
     }
 
-    let result = blueprints.iter().map(|b| b.quality_level).sum();
+    let result: usize = blueprints.iter().map(|b| b.quality_level).sum();
 
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
+    Ok(Answer::from(result))
+}
 
-    result
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_blueprints(data)?, params)
 }
 
-pub fn solution2(data: String) -> usize {
-    let mut blueprints = read_blueprints(data).into_iter().filter(|b| b.id <= 3).collect::<Vec<Blueprint>>();
+pub(crate) fn solve2(blueprints: Vec<Blueprint>, _params: &Params) -> Result<Answer, SolveError> {
+    let mut blueprints = blueprints.into_iter().filter(|b| b.id <= 3).collect::<Vec<Blueprint>>();
 
     for blueprint in blueprints.iter_mut() {
         blueprint.print();
         analyze_blueprint(blueprint, 32);
     }
 
-    let result = blueprints.iter().map(|b| b.quality_level / b.id as usize).product();
-
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
+    let result: usize = blueprints.iter().map(|b| b.quality_level / b.id as usize).product();
 
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
@@ -71,7 +74,7 @@ impl fmt::Display for Robot {
 }
 
 #[derive(Debug, Clone)]
-struct Blueprint {
+pub(crate) struct Blueprint {
     id: u8,
     robots: Vec<Robot>,
     quality_level: usize
@@ -83,17 +86,17 @@ impl Blueprint {
     }
 
     fn print(&self) {
-        println!("");
-        println!("Blueprint #{}", self.id);
-        println!("---------");
+        debug!();
+        debug!("Blueprint #{}", self.id);
+        debug!("---------");
         for robot in self.robots.iter() {
-            println!("{}", robot);
+            debug!("{}", robot);
         }
-        println!("");
+        debug!();
     }
 }
 
-fn read_blueprints(data: String) -> Vec<Blueprint> {
+pub(crate) fn read_blueprints(data: &str) -> Result<Vec<Blueprint>, ParseError> {
     let blueprint_regex = Regex::new(
         r"(?x)
         ^
@@ -107,30 +110,26 @@ fn read_blueprints(data: String) -> Vec<Blueprint> {
     .unwrap();
 
     data.lines()
-        .map(|line| {
-            let captures = blueprint_regex.captures(line).unwrap();
-            let id = captures[1].parse::<u8>().unwrap();
+        .enumerate()
+        .map(|(idx, line)| {
+            let captures = blueprint_regex.captures(line).ok_or_else(|| ParseError::at_line(idx, line, "Invalid blueprint"))?;
+            let id = parse_value::<u8>(idx, line, &captures[1])?;
+            let cost = |n: usize| parse_value::<usize>(idx, line, &captures[n]);
             let robots = vec![
                 // In order of material
-                Robot { kind: Material::Ore, cost: vec![Resource { kind: Material::Ore, quantity: captures[2].parse::<usize>().unwrap() }] },
-                Robot { kind: Material::Clay, cost: vec![Resource { kind: Material::Ore, quantity: captures[3].parse::<usize>().unwrap() }] },
+                Robot { kind: Material::Ore, cost: vec![Resource { kind: Material::Ore, quantity: cost(2)? }] },
+                Robot { kind: Material::Clay, cost: vec![Resource { kind: Material::Ore, quantity: cost(3)? }] },
                 Robot {
                     kind: Material::Obsidian,
-                    cost: vec![
-                        Resource { kind: Material::Ore, quantity: captures[4].parse::<usize>().unwrap() },
-                        Resource { kind: Material::Clay, quantity: captures[5].parse::<usize>().unwrap() },
-                    ]
+                    cost: vec![Resource { kind: Material::Ore, quantity: cost(4)? }, Resource { kind: Material::Clay, quantity: cost(5)? }]
                 },
                 Robot {
                     kind: Material::Geode,
-                    cost: vec![
-                        Resource { kind: Material::Ore, quantity: captures[6].parse::<usize>().unwrap() },
-                        Resource { kind: Material::Obsidian, quantity: captures[7].parse::<usize>().unwrap() },
-                    ]
+                    cost: vec![Resource { kind: Material::Ore, quantity: cost(6)? }, Resource { kind: Material::Obsidian, quantity: cost(7)? }]
                 },
             ];
 
-            Blueprint::new(id, robots)
+            Ok(Blueprint::new(id, robots))
         })
         .collect()
 }
@@ -155,12 +154,11 @@ impl FactoryState {
     }
 
     fn print(&self) {
-        println!("State --> robots: {:?}, resources: {:?}", self.robots, self.resources);
-        #[cfg(test)]
+        debug!("State --> robots: {:?}, resources: {:?}", self.robots, self.resources);
         for (time, material) in self.robot_creation_history.iter() {
-            println!("  > time remaining {:2} --> {:?} robot created", time, material);
+            trace!("  > time remaining {:2} --> {:?} robot created", time, material);
         }
-        println!("------");
+        debug!("------");
     }
 
     fn get_buildable_robots(&self, blueprint: &Blueprint) -> Vec<Material> {
@@ -240,17 +238,17 @@ fn build_resource_map(blueprint: &Blueprint) -> HashMap<Material, usize> {
 
 // Depth first search solution (completes in less than one minute)
 fn analyze_blueprint(blueprint: &mut Blueprint, duration: usize) {
-    println!("Analyzing blueprint #{}...", blueprint.id);
-
     // Building max resource quantity need to build any robot
     let resources_map = build_resource_map(blueprint);
 
     let mut best_score = (0, 0, 0, 0);
     let mut factory_states = vec![FactoryState::new(duration)];
     let mut processed_states = vec![];
+    let mut progress = Progress::new(&format!("Blueprint #{} states", blueprint.id), None);
     while factory_states.len() > 0 {
         // Current state
         let mut current_state = factory_states.pop().unwrap();
+        progress.inc();
 
         // Choose next robot to build (There will always be at least the Geode robot, even after filtering)
         let mut robots_to_build: Vec<Material> = current_state
@@ -304,6 +302,7 @@ fn analyze_blueprint(blueprint: &mut Blueprint, duration: usize) {
             let max_score = current_state.geode_production_score();
             if max_score > best_score {
                 best_score = max_score;
+                progress.set_message(format!("best score = {:?}", best_score));
             }
 
             processed_states.push(current_state);
@@ -313,13 +312,15 @@ fn analyze_blueprint(blueprint: &mut Blueprint, duration: usize) {
         factory_states = factory_states.drain(..).filter(|state| state.geode_production_score() >= best_score).collect();
     }
 
+    progress.finish();
+
     // Qualifying blueprint quality
-    println!("Analyzing {} states...", processed_states.len());
+    info!("Analyzing {} states...", processed_states.len());
     if let Some(optimal_state) = processed_states.iter().max_by_key(|s| (s.resources[Material::Geode as usize], s.resources[Material::Obsidian as usize])) {
         optimal_state.print();
         blueprint.quality_level = blueprint.id as usize * optimal_state.resources[Material::Geode as usize];
     }
-    println!("Quality level --> {}", blueprint.quality_level);
+    info!("Quality level --> {}", blueprint.quality_level);
 }
 
 /////////////////////////////////////////////////
@@ -337,12 +338,12 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(33, solution1(data));
+        assert_eq!(33, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(56 * 62, solution2(data));
+        assert_eq!(56 * 62, solution2(&data, &Params::new()).unwrap());
     }
 }
@@ -1,62 +1,63 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
+use crate::progress::Progress;
+use crate::{info, trace};
 use itertools::Itertools;
 use std::rc::Rc;
 
-pub fn solution1(data: String) -> isize {
-    let values = read_values(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_values(data)?, params)
+}
 
-    #[cfg(test)]
-    println!("{:?}", values);
+pub(crate) fn solve1(values: Vec<isize>, _params: &Params) -> Result<Answer, SolveError> {
+    trace!("{:?}", values);
 
     let decryp_values = decrypt(&values, 1, None);
 
-    #[cfg(test)]
-    println!("{:?}", decryp_values);
+    trace!("{:?}", decryp_values);
 
     let (zero_idx, _) = decryp_values.iter().find_position(|v| **v == 0).unwrap();
     let groove_coordinates: Vec<isize> = vec![1000, 2000, 3000].iter().map(|offset| decryp_values[(zero_idx + *offset) % decryp_values.len()]).collect();
-    let result = groove_coordinates.iter().sum();
+    let result: isize = groove_coordinates.iter().sum();
+
+    info!("=========================");
+    info!("Zero index: {zero_idx}");
+    info!("Groove coordinates: {:?}", groove_coordinates);
 
-    println!("=========================");
-    println!("Zero index: {zero_idx}");
-    println!("Groove coordinates: {:?}", groove_coordinates);
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
+    Ok(Answer::from(result))
+}
 
-    result
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_values(data)?, params)
 }
 
-pub fn solution2(data: String) -> isize {
-    let values = read_values(data);
+pub(crate) fn solve2(values: Vec<isize>, params: &Params) -> Result<Answer, SolveError> {
+    let decryption_key = params.get("decryption_key", DECRYPTION_KEY)?;
 
-    #[cfg(test)]
-    println!("{:?}", values);
+    trace!("{:?}", values);
 
-    let decryp_values = decrypt(&values, 10, Some(DECRYPTION_KEY));
+    let decryp_values = decrypt(&values, 10, Some(decryption_key));
 
-    #[cfg(test)]
-    println!("{:?}", decryp_values);
+    trace!("{:?}", decryp_values);
 
     let (zero_idx, _) = decryp_values.iter().find_position(|v| **v == 0).unwrap();
     let groove_coordinates: Vec<isize> = vec![1000, 2000, 3000].iter().map(|offset| decryp_values[(zero_idx + *offset) % decryp_values.len()]).collect();
-    let result = groove_coordinates.iter().sum();
+    let result: isize = groove_coordinates.iter().sum();
 
-    println!("=========================");
-    println!("Zero index: {zero_idx}");
-    println!("Groove coordinates: {:?}", groove_coordinates);
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
+    info!("=========================");
+    info!("Zero index: {zero_idx}");
+    info!("Groove coordinates: {:?}", groove_coordinates);
 
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
 
 const DECRYPTION_KEY: isize = 811589153;
 
-fn read_values(data: String) -> Vec<isize> {
-    data.lines().map(|line| line.parse::<isize>().unwrap()).collect()
+pub(crate) fn read_values(data: &str) -> Result<Vec<isize>, ParseError> {
+    Ok(data.lines().map(|line| line.parse::<isize>().unwrap()).collect())
 }
 
 fn decrypt(values: &Vec<isize>, nb_rounds: usize, decryption_key: Option<isize>) -> Vec<isize> {
@@ -71,12 +72,14 @@ fn decrypt(values: &Vec<isize>, nb_rounds: usize, decryption_key: Option<isize>)
     // Circular list with no start/end position (i.e: value positions could move around, but relative ordering will be conserved)
     let mut new_values: Vec<Rc<isize>> = value_pointers.iter().map(|vp| Rc::clone(vp)).collect();
 
-    println!("Decrypting...");
     let nb_values = values.len();
+    let mut progress = Progress::new("Mixed value", Some((nb_rounds * nb_values) as u64));
     for round_idx in 0..nb_rounds {
-        println!("Round #{}", round_idx + 1);
+        progress.set_message(format!("round {}/{nb_rounds}", round_idx + 1));
 
         for idx in 0..values.len() {
+            progress.inc();
+
             // Finding current value to move from original array in at most O(n)
             let (current_idx, current_value) = new_values.iter().find_position(|vp| Rc::ptr_eq(vp, &value_pointers[idx])).unwrap();
 
@@ -126,12 +129,12 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(3, solution1(data));
+        assert_eq!(3, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(1623178306, solution2(data));
+        assert_eq!(1623178306, solution2(&data, &Params::new()).unwrap());
     }
 }
@@ -1,32 +1,33 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::{parse_value, ParseError};
+use crate::debug;
+use crate::progress::Progress;
 use std::{collections::HashMap, rc::Rc, cell::RefCell};
 
 use itertools::Itertools;
 use regex::Regex;
 
-pub fn solution1(data: String) -> isize {
-    let mut monkeys_map = read_monkey_data(data, false);
+pub fn solution1(data: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let mut monkeys_map = read_monkey_data(data, false)?;
     let result = find_root_number(&mut monkeys_map);
 
     for (name, monkey) in monkeys_map.iter().sorted_by_key(|(name, _)| *name) {
-        println!("{} --> {:?}", name, monkey.as_ref().borrow().yelled_number);
+        debug!("{} --> {:?}", name, monkey.as_ref().borrow().yelled_number);
     }
 
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
-pub fn solution2(data: String) -> isize {
-    let mut monkeys_map = read_monkey_data(data, true);
-    let result = find_human_number(&mut monkeys_map, 30000);
+const STARTING_GUESS: isize = 30000;
+
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    let starting_guess = params.get("starting_guess", STARTING_GUESS)?;
 
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
+    let mut monkeys_map = read_monkey_data(data, true)?;
+    let result = find_human_number(&mut monkeys_map, starting_guess);
 
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
@@ -37,12 +38,15 @@ struct Monkey {
     operation: Option<Box<dyn Fn(&HashMap<String, Rc<RefCell<Monkey>>>) -> Option<isize>>>
 }
 
-fn read_monkey_data(data: String, fix_data: bool) -> HashMap<String, Rc<RefCell<Monkey>>> {
+fn read_monkey_data(data: &str, fix_data: bool) -> Result<HashMap<String, Rc<RefCell<Monkey>>>, ParseError> {
     let mut monkeys_map = HashMap::new();
+    let mut monkey_references = vec![]; // Monkey names waited for, checked once every monkey is read
 
     let monkey_regex = Regex::new(r"^(\w+): (?:(\d+)|(\w+) (.) (\w+))$").unwrap();
-    data.lines().for_each(|line| {
-        let captures = monkey_regex.captures(line).unwrap();
+    for (idx, line) in data.lines().enumerate() {
+        let captures = monkey_regex
+            .captures(line)
+            .ok_or_else(|| ParseError::at_line(idx, line, "Invalid monkey (expected \"name: number\" or \"name: name1 op name2\")"))?;
 
         let name = String::from(&captures[1]);
         if let Some(group) = captures.get(2) {
@@ -50,10 +54,14 @@ fn read_monkey_data(data: String, fix_data: bool) -> HashMap<String, Rc<RefCell<
                 Monkey { _name: name.clone(), yelled_number: None, operation: None }
             }
             else {
-                Monkey { _name: name.clone(), yelled_number: Some(group.as_str().parse::<isize>().unwrap()), operation: None }
+                Monkey { _name: name.clone(), yelled_number: Some(parse_value::<isize>(idx, line, group.as_str())?), operation: None }
             };
             monkeys_map.insert(name, Rc::new(RefCell::new(monkey)));
-            return;
+            continue;
+        }
+
+        for group in [captures.get(3), captures.get(5)].into_iter().flatten() {
+            monkey_references.push((idx, line, group.as_str()));
         }
 
         let op = &captures[4];
@@ -94,15 +102,25 @@ fn read_monkey_data(data: String, fix_data: bool) -> HashMap<String, Rc<RefCell<
                         _ => None
                     }
                 }),
-                _ => panic!("Invalid operation '{op}'")
+                _ => return Err(ParseError::at_text(idx, line, op, &format!("Invalid operation \"{op}\" (expected +, -, * or /)")))
             };
         }
 
         let monkey = Monkey { _name: name.clone(), yelled_number: None, operation: Some(operation) };
         monkeys_map.insert(name, Rc::new(RefCell::new(monkey)));
-    });
+    }
+
+    if let Some((idx, line, name)) = monkey_references.into_iter().find(|(_, _, name)| !monkeys_map.contains_key(*name)) {
+        return Err(ParseError::at_text(idx, line, name, &format!("Unknown monkey \"{name}\"")));
+    }
+    let required_names: &[&str] = if fix_data { &["root", "humn"] } else { &["root"] };
+    for name in required_names {
+        if !monkeys_map.contains_key(*name) {
+            return Err(ParseError::at_end_of_input(data, &format!("Missing \"{name}\" monkey")));
+        }
+    }
 
-    monkeys_map
+    Ok(monkeys_map)
 }
 
 fn find_root_number(monkeys_map: &mut HashMap<String, Rc<RefCell<Monkey>>>) -> isize {
@@ -129,9 +147,11 @@ fn find_root_number(monkeys_map: &mut HashMap<String, Rc<RefCell<Monkey>>>) -> i
 fn find_human_number(monkeys_map: &mut HashMap<String, Rc<RefCell<Monkey>>>, starting_guess: isize) -> isize {
     let mut human_number: isize = starting_guess;
 
+    let mut progress = Progress::new("Guess", None);
     loop {
+        progress.inc();
         if human_number % 1000 == 0 {
-            println!("Guessing value {human_number}...");
+            progress.set_message(format!("value = {human_number}"));
         }
 
         loop {
@@ -183,12 +203,27 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(152, solution1(data));
+        assert_eq!(152, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(301, solution2(data));
+        assert_eq!(301, solution2(&data, &Params::new().with("starting_guess", 0)).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let data = read_test_file().replace("dbpl: 5", "dbpl: five");
+        let error = read_monkey_data(&data, false).err().unwrap();
+        assert_eq!((2, 1, "Invalid monkey (expected \"name: number\" or \"name: name1 op name2\")"), (error.line_number, error.column, &error.message[..]));
+
+        let data = read_test_file().replace("cczh: sllz + lgvd", "cczh: sllz % lgvd");
+        let error = read_monkey_data(&data, false).err().unwrap();
+        assert_eq!((3, 12, "Invalid operation \"%\" (expected +, -, * or /)"), (error.line_number, error.column, &error.message[..]));
+
+        let data = read_test_file().replace("cczh: sllz + lgvd", "cczh: sllz + abcd");
+        let error = read_monkey_data(&data, false).err().unwrap();
+        assert_eq!((3, 14, "Unknown monkey \"abcd\""), (error.line_number, error.column, &error.message[..]));
     }
 }
@@ -1,53 +1,58 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::{parse_value, ParseError};
+use crate::info;
 use std::collections::HashMap;
 
 use regex::Regex;
 
-use crate::navigation::{get_adjacent_locations_in_direction, get_direction_after_rotation, Coordinate, Direction, Grid, Rotation};
+use crate::grid::GridLike;
+use crate::navigation::{get_adjacent_locations_in_direction, get_direction_after_rotation, Coordinate, Direction, Grid, GridMap, Rotation};
 
-pub fn solution1(data: String) -> isize {
-    let (start, path_instructions, mut board_map) = read_board_map_data(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_board_map_data(data)?, params)
+}
+
+pub(crate) fn solve1((start, path_instructions, mut board_map): BoardMapData, _params: &Params) -> Result<Answer, SolveError> {
     board_map.print(&Symbol::Void, &symbol_to_char);
 
     // TODO: mark path as we go to print later...
-    let (end, direction) = find_end_location(&start, &path_instructions, &mut board_map);
+    let (end, direction) = find_end_location(&start, &path_instructions, &mut board_map)?;
 
     let result = get_password(&end, &direction);
 
-    println!("=========================");
-    println!("Start: {:?} --> {:?}", start, Direction::Right);
-    println!("End  : {:?} --> {:?}", end, direction);
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
+    info!("=========================");
+    info!("Start: {:?} --> {:?}", start, Direction::Right);
+    info!("End  : {:?} --> {:?}", end, direction);
 
-    result
+    Ok(Answer::from(result))
 }
 
-pub fn solution2(data: String) -> isize {
-    let (start, path_instructions, mut board_map) = read_board_map_data(data);
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_board_map_data(data)?, params)
+}
+
+pub(crate) fn solve2((start, path_instructions, mut board_map): BoardMapData, _params: &Params) -> Result<Answer, SolveError> {
     board_map.print(&Symbol::Void, &symbol_to_char);
 
     let folding_map = map_folding_border_locations(&board_map);
 
     // TODO: mark path as we go to print later...
-    let (end, direction) = find_end_location(&start, &path_instructions, &mut board_map);
+    let (end, direction) = find_end_location(&start, &path_instructions, &mut board_map)?;
 
     let result = get_password(&end, &direction);
 
-    println!("=========================");
-    println!("Start: {:?} --> {:?}", start, Direction::Right);
-    println!("End  : {:?} --> {:?}", end, direction);
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
+    info!("=========================");
+    info!("Start: {:?} --> {:?}", start, Direction::Right);
+    info!("End  : {:?} --> {:?}", end, direction);
 
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
 
 #[derive(Debug, Eq, PartialEq, Hash)]
-enum Symbol {
+pub(crate) enum Symbol {
     Void,
     Empty,
     Wall
@@ -62,51 +67,39 @@ fn symbol_to_char(symbol: &Symbol) -> &str {
 }
 
 #[derive(Debug)]
-enum PathInstruction {
+pub(crate) enum PathInstruction {
     Move(usize),
     Rotate(Rotation)
 }
 
-fn read_board_map_data(data: String) -> (Coordinate<isize>, Vec<PathInstruction>, Grid<isize, Symbol>) {
-    let mut start: Coordinate<isize> = Coordinate { x: 0, y: 0 };
-    let mut path_instructions = vec![];
-    let mut board_map = Grid::new();
-
+/// Start location and path instructions, with board map
+type BoardMapData = (Coordinate<isize>, Vec<PathInstruction>, Grid<isize, Symbol>);
+
+pub(crate) fn read_board_map_data(data: &str) -> Result<BoardMapData, ParseError> {
+    // Board map, until empty line
+    let board_description = data.lines().take_while(|line| !line.is_empty()).collect::<Vec<&str>>().join("\n");
+    let board_map = Grid::try_parse(&board_description, |char| match char {
+        '.' => Ok(Some(Symbol::Empty)),
+        '#' => Ok(Some(Symbol::Wall)),
+        ' ' => Ok(None), // Void
+        _ => Err(String::from("Invalid board map character (expected \"#\", \".\" or \" \")"))
+    })?;
+    let start = *board_map.get_locations_with_value(&Symbol::Empty).first().ok_or_else(|| ParseError::at_end_of_input(&board_description, "Missing open tile \".\""))?; // Leftmost open tile of top row
+
+    // Last path description line
     let path_description_regex = Regex::new(r"(\d+|R|L)").unwrap();
-
-    let mut y = 0;
-    let mut lines_iterator = data.lines();
-    while let Some(line) = lines_iterator.next() {
-        if line == "" {
-            // Last path description line
-            let line = lines_iterator.next().unwrap();
-            for capture in path_description_regex.captures_iter(line) {
-                match &capture[1] {
-                    "R" => path_instructions.push(PathInstruction::Rotate(Rotation::Clockwise)),
-                    "L" => path_instructions.push(PathInstruction::Rotate(Rotation::CounterClockwise)),
-                    _ => path_instructions.push(PathInstruction::Move(capture[1].parse::<usize>().unwrap()))
-                }
+    let mut path_instructions = vec![];
+    if let Some((idx, line)) = data.lines().enumerate().skip_while(|(_, line)| !line.is_empty()).nth(1) {
+        for capture in path_description_regex.captures_iter(line) {
+            match &capture[1] {
+                "R" => path_instructions.push(PathInstruction::Rotate(Rotation::Clockwise)),
+                "L" => path_instructions.push(PathInstruction::Rotate(Rotation::CounterClockwise)),
+                _ => path_instructions.push(PathInstruction::Move(parse_value::<usize>(idx, line, &capture[1])?))
             }
-            break;
         }
-
-        // Board map
-        line.chars().enumerate().for_each(|(x, char)| {
-            if char == '.' {
-                if y == 0 && start.x == 0 {
-                    start = Coordinate { x: x as isize, y }
-                }
-                board_map.add_location(Coordinate { x: x as isize, y }, Symbol::Empty);
-            }
-            else if char == '#' {
-                board_map.add_location(Coordinate { x: x as isize, y }, Symbol::Wall);
-            }
-        });
-
-        y += 1;
     }
 
-    (start, path_instructions, board_map)
+    Ok((start, path_instructions, board_map))
 }
 
 fn get_password(location: &Coordinate<isize>, direction: &Direction) -> isize {
@@ -120,7 +113,11 @@ fn get_password(location: &Coordinate<isize>, direction: &Direction) -> isize {
     1000 * (location.y + 1) + 4 * (location.x + 1) + direction_value
 }
 
-fn find_end_location(start: &Coordinate<isize>, instructions: &Vec<PathInstruction>, map: &mut Grid<isize, Symbol>) -> (Coordinate<isize>, Direction) {
+fn find_end_location(
+    start: &Coordinate<isize>,
+    instructions: &Vec<PathInstruction>,
+    map: &mut Grid<isize, Symbol>
+) -> Result<(Coordinate<isize>, Direction), SolveError> {
     let mut direction = Direction::Right;
     let mut current = start.clone();
 
@@ -139,7 +136,10 @@ fn find_end_location(start: &Coordinate<isize>, instructions: &Vec<PathInstructi
                             match map.get_value(&looping_location) {
                                 Some(Symbol::Wall) => break,
                                 Some(Symbol::Empty) => looping_location,
-                                _ => panic!("Looping into void {:?} {:?}--> {:?}", current, direction, looping_location)
+                                _ => {
+                                    let reason = format!("Looping into void {:?} {:?} --> {:?}", current, direction, looping_location);
+                                    return Err(SolveError::NoSolution(reason));
+                                }
                             }
                         }
                     }
@@ -152,7 +152,7 @@ fn find_end_location(start: &Coordinate<isize>, instructions: &Vec<PathInstructi
         }
     }
 
-    (current, direction)
+    Ok((current, direction))
 }
 
 fn find_looping_location(location: &Coordinate<isize>, direction: &Direction, map: &Grid<isize, Symbol>) -> Coordinate<isize> {
@@ -275,12 +275,27 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(6032, solution1(data));
+        assert_eq!(6032, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(5031, solution2(data));
+        assert_eq!(5031, solution2(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let data = read_test_file().replace(".", "#");
+        let error = read_board_map_data(&data).err().unwrap();
+        assert_eq!((12, 17, "Missing open tile \".\""), (error.line_number, error.column, &error.message[..]));
+
+        let data = read_test_file().replacen("...#", "..x#", 1);
+        let error = read_board_map_data(&data).err().unwrap();
+        assert_eq!((1, 11, "Invalid board map character (expected \"#\", \".\" or \" \")"), (error.line_number, error.column, &error.message[..]));
+
+        let data = read_test_file().replace("10R5", "99999999999999999999R5");
+        let error = read_board_map_data(&data).err().unwrap();
+        assert_eq!((14, 1, "Invalid usize \"99999999999999999999\""), (error.line_number, error.column, &error.message[..]));
     }
 }
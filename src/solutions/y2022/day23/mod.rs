@@ -1,12 +1,18 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
+use crate::{debug, info};
+use crate::grid::GridLike;
 use crate::navigation::{
     get_adjacent_diagonal_locations, get_adjacent_locations_in_cardinal_direction, get_adjacent_orthogonal_locations, CardinalDirection, Coordinate, Direction,
-    Grid
+    Grid, GridMap
 };
-use std::collections::HashMap;
 
-pub fn solution1(data: String) -> usize {
-    let mut grove_map = read_grove_map(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_grove_map(data)?, params)
+}
 
+pub(crate) fn solve1(mut grove_map: Grid<isize, Symbol>, _params: &Params) -> Result<Answer, SolveError> {
     simulate_rounds(&mut grove_map, Some(10));
 
     // Trimming map to remove extra empty locations on border
@@ -14,33 +20,29 @@ pub fn solution1(data: String) -> usize {
 
     let result = (grove_map.width() * grove_map.height()) as usize - grove_map.get_mapped_locations_with_value(&Symbol::Elf).len();
 
-    println!("=========================");
+    debug!("=========================");
     grove_map.print(&Symbol::Empty, &symbol_to_char);
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
 
-    result
+    Ok(Answer::from(result))
 }
 
-pub fn solution2(data: String) -> usize {
-    let mut grove_map = read_grove_map(data);
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_grove_map(data)?, params)
+}
 
+pub(crate) fn solve2(mut grove_map: Grid<isize, Symbol>, _params: &Params) -> Result<Answer, SolveError> {
     let result = simulate_rounds(&mut grove_map, None);
 
-    println!("=========================");
+    debug!("=========================");
     grove_map.print(&Symbol::Empty, &symbol_to_char);
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
 
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
 
 #[derive(Debug, Eq, PartialEq, Hash)]
-enum Symbol {
+pub(crate) enum Symbol {
     Elf,
     Empty
 }
@@ -52,22 +54,12 @@ fn symbol_to_char(symbol: &Symbol) -> &str {
     }
 }
 
-fn read_grove_map(data: String) -> Grid<isize, Symbol> {
-    let mut grove_map = Grid::new();
-
-    data.lines().enumerate().for_each(|(y, line)| {
-        line.chars().enumerate().for_each(|(x, char)| {
-            let value = match char {
-                '.' => Symbol::Empty,
-                '#' => Symbol::Elf,
-                _ => panic!("Invalid char {char}")
-            };
-
-            grove_map.add_location(Coordinate { x: x as isize, y: y as isize }, value);
-        });
-    });
-
-    grove_map
+pub(crate) fn read_grove_map(data: &str) -> Result<Grid<isize, Symbol>, ParseError> {
+    Grid::try_parse(data, |char| match char {
+        '.' => Ok(Some(Symbol::Empty)),
+        '#' => Ok(Some(Symbol::Elf)),
+        _ => Err(String::from("Invalid grove map character (expected \"#\" or \".\")"))
+    })
 }
 
 fn simulate_rounds(grove_map: &mut Grid<isize, Symbol>, max_nb_rounds: Option<usize>) -> usize {
@@ -77,11 +69,11 @@ fn simulate_rounds(grove_map: &mut Grid<isize, Symbol>, max_nb_rounds: Option<us
     while max_nb_rounds == None || round_idx < max_nb_rounds.unwrap() {
         // Beginning of round
         if round_idx % 10 == 0 {
-            println!("Round #{}", round_idx + 1);
+            info!("Round #{}", round_idx + 1);
         }
 
         // 1) Getting all proposed new locations
-        let mut new_locations_map: HashMap<Coordinate<isize>, Vec<Coordinate<isize>>> = HashMap::new();
+        let mut new_locations_map: Grid<isize, Vec<Coordinate<isize>>> = Grid::new();
         for elf_location in grove_map.get_locations_with_value(&Symbol::Elf).into_iter() {
             let adjacent_locations = [
                 get_adjacent_orthogonal_locations(&elf_location), // North, East, South, West
@@ -101,11 +93,7 @@ fn simulate_rounds(grove_map: &mut Grid<isize, Symbol>, max_nb_rounds: Option<us
                         // North, NE, NW
                         if [&adjacent_values[0], &adjacent_values[4], &adjacent_values[7]].iter().all(|v| v.unwrap_or(&Symbol::Empty) == &Symbol::Empty) {
                             let new_location = get_adjacent_locations_in_cardinal_direction(&elf_location, &CardinalDirection::North);
-                            if new_locations_map.contains_key(&new_location) == false {
-                                new_locations_map.insert(new_location.clone(), vec![]);
-                            }
-
-                            new_locations_map.get_mut(&new_location).unwrap().push(elf_location);
+                            new_locations_map.entry(new_location).or_default().push(elf_location);
                             break;
                         }
                     },
@@ -113,11 +101,7 @@ fn simulate_rounds(grove_map: &mut Grid<isize, Symbol>, max_nb_rounds: Option<us
                         // East, NE, SE
                         if [&adjacent_values[1], &adjacent_values[4], &adjacent_values[5]].iter().all(|v| v.unwrap_or(&Symbol::Empty) == &Symbol::Empty) {
                             let new_location = get_adjacent_locations_in_cardinal_direction(&elf_location, &CardinalDirection::East);
-                            if new_locations_map.contains_key(&new_location) == false {
-                                new_locations_map.insert(new_location.clone(), vec![]);
-                            }
-
-                            new_locations_map.get_mut(&new_location).unwrap().push(elf_location);
+                            new_locations_map.entry(new_location).or_default().push(elf_location);
                             break;
                         }
                     },
@@ -125,11 +109,7 @@ fn simulate_rounds(grove_map: &mut Grid<isize, Symbol>, max_nb_rounds: Option<us
                         // South, SE, SW
                         if [&adjacent_values[2], &adjacent_values[5], &adjacent_values[6]].iter().all(|v| v.unwrap_or(&Symbol::Empty) == &Symbol::Empty) {
                             let new_location = get_adjacent_locations_in_cardinal_direction(&elf_location, &CardinalDirection::South);
-                            if new_locations_map.contains_key(&new_location) == false {
-                                new_locations_map.insert(new_location.clone(), vec![]);
-                            }
-
-                            new_locations_map.get_mut(&new_location).unwrap().push(elf_location);
+                            new_locations_map.entry(new_location).or_default().push(elf_location);
                             break;
                         }
                     },
@@ -137,11 +117,7 @@ fn simulate_rounds(grove_map: &mut Grid<isize, Symbol>, max_nb_rounds: Option<us
                         // West, SW, NW
                         if [&adjacent_values[3], &adjacent_values[6], &adjacent_values[7]].iter().all(|v| v.unwrap_or(&Symbol::Empty) == &Symbol::Empty) {
                             let new_location = get_adjacent_locations_in_cardinal_direction(&elf_location, &CardinalDirection::West);
-                            if new_locations_map.contains_key(&new_location) == false {
-                                new_locations_map.insert(new_location.clone(), vec![]);
-                            }
-
-                            new_locations_map.get_mut(&new_location).unwrap().push(elf_location);
+                            new_locations_map.entry(new_location).or_default().push(elf_location);
                             break;
                         }
                     }
@@ -149,8 +125,8 @@ fn simulate_rounds(grove_map: &mut Grid<isize, Symbol>, max_nb_rounds: Option<us
             }
         }
 
-        if new_locations_map.len() == 0 {
-            println!("End of process");
+        if new_locations_map.is_empty() {
+            info!("End of process");
             break; // End of process
         }
 
@@ -186,12 +162,19 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(110, solution1(data));
+        assert_eq!(110, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(20, solution2(data));
+        assert_eq!(20, solution2(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let data = read_test_file().replacen("....#..", "....#.x", 1);
+        let error = read_grove_map(&data).err().unwrap();
+        assert_eq!((1, 7, "Invalid grove map character (expected \"#\" or \".\")"), (error.line_number, error.column, &error.message[..]));
     }
 }
@@ -1,71 +1,74 @@
-use std::collections::{HashMap, HashSet};
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
+use crate::{info, trace};
+use std::collections::HashSet;
 
-use crate::navigation::{get_adjacent_locations_in_direction, get_adjacent_orthogonal_locations, Coordinate, Direction, Grid, Path};
+use crate::grid::GridLike;
+use crate::navigation::{get_adjacent_locations_in_direction, get_adjacent_orthogonal_locations, Coordinate, Direction, Grid, GridMap, Path};
 
-pub fn solution1(data: String) -> usize {
-    let (start, goal, mut valley_map) = read_valley_map(data);
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_valley_map(data)?, params)
+}
+
+pub(crate) fn solve1((start, goal, mut valley_map): ValleyMapData, _params: &Params) -> Result<Answer, SolveError> {
     let mut blizzard_locations_map = map_blizzard_locations(&valley_map);
 
-    println!("Start: {:?} --> Goal: {:?}", start, goal);
+    info!("Start: {:?} --> Goal: {:?}", start, goal);
     valley_map.print(&Symbol::Empty, &symbol_to_char);
 
     let path = match find_shortest_path(&start, &goal, &mut valley_map, &mut blizzard_locations_map) {
         Some(path) => path,
-        None => panic!("No path found !")
+        None => return Err(SolveError::NoSolution(format!("No path found from {:?} to {:?}", start, goal)))
     };
 
     valley_map.print(&Symbol::Empty, &symbol_to_char);
 
     let result = path.locations.len() - 1; // Excluding starting position
 
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
+    Ok(Answer::from(result))
+}
 
-    result
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_valley_map(data)?, params)
 }
 
-pub fn solution2(data: String) -> usize {
-    let (start, goal, mut valley_map) = read_valley_map(data);
+pub(crate) fn solve2((start, goal, mut valley_map): ValleyMapData, _params: &Params) -> Result<Answer, SolveError> {
     let mut blizzard_locations_map = map_blizzard_locations(&valley_map);
 
-    println!("Start: {:?} --> Goal: {:?}", start, goal);
+    info!("Start: {:?} --> Goal: {:?}", start, goal);
     valley_map.print(&Symbol::Empty, &symbol_to_char);
 
     // First trip
     let path1 = match find_shortest_path(&start, &goal, &mut valley_map, &mut blizzard_locations_map) {
         Some(path) => path,
-        None => panic!("No path found !")
+        None => return Err(SolveError::NoSolution(format!("No path found from {:?} to {:?}", start, goal)))
     };
     valley_map.print(&Symbol::Empty, &symbol_to_char);
 
     // Going back for snack
     let path2 = match find_shortest_path(&goal, &start, &mut valley_map, &mut blizzard_locations_map) {
         Some(path) => path,
-        None => panic!("No path found !")
+        None => return Err(SolveError::NoSolution(format!("No path found back from {:?} to {:?}", goal, start)))
     };
     valley_map.print(&Symbol::Empty, &symbol_to_char);
 
     // Final trip
     let path3 = match find_shortest_path(&start, &goal, &mut valley_map, &mut blizzard_locations_map) {
         Some(path) => path,
-        None => panic!("No path found !")
+        None => return Err(SolveError::NoSolution(format!("No path found from {:?} to {:?}", start, goal)))
     };
     valley_map.print(&Symbol::Empty, &symbol_to_char);
 
     let result = path1.locations.len() + path2.locations.len() + path3.locations.len() - 3; // Excluding starting positions
 
-    println!("=========================");
-    println!("Solution2: {result}");
-    println!("=========================");
-
-    result
+    Ok(Answer::from(result))
 }
 
 /////////////////////////////////////////////////
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
-enum Symbol {
+pub(crate) enum Symbol {
     Wall,
     Empty,
     Blizzard(Direction),
@@ -86,85 +89,58 @@ fn symbol_to_char(symbol: &Symbol) -> &str {
     }
 }
 
-fn read_valley_map(data: String) -> (Coordinate<isize>, Coordinate<isize>, Grid<isize, Symbol>) {
-    let mut start: Coordinate<isize> = Coordinate { x: 0, y: 0 };
-    let mut goal: Coordinate<isize> = Coordinate { x: 0, y: 0 };
-    let mut valley_map = Grid::new();
-
-    let nb_lines = data.lines().count();
-    data.lines().enumerate().for_each(|(y, line)| {
-        line.chars().enumerate().for_each(|(x, char)| {
-            let value = match char {
-                '.' => {
-                    if y == 0 {
-                        start = Coordinate { x: x as isize, y: y as isize };
-                    }
-
-                    if y == nb_lines - 1 {
-                        goal = Coordinate { x: x as isize, y: y as isize };
-                    }
-
-                    Symbol::Empty
-                },
-                '#' => Symbol::Wall,
-                '^' => Symbol::Blizzard(Direction::Up),
-                '>' => Symbol::Blizzard(Direction::Right),
-                '<' => Symbol::Blizzard(Direction::Left),
-                'v' => Symbol::Blizzard(Direction::Down),
-                _ => panic!("Invalid char {char}")
-            };
-
-            valley_map.add_location(Coordinate { x: x as isize, y: y as isize }, value);
-        });
-    });
-
-    (start, goal, valley_map)
-}
+/// Start and goal locations, with valley map
+type ValleyMapData = (Coordinate<isize>, Coordinate<isize>, Grid<isize, Symbol>);
+
+pub(crate) fn read_valley_map(data: &str) -> Result<ValleyMapData, ParseError> {
+    let valley_map = Grid::try_parse(data, |char| match char {
+        '.' => Ok(Some(Symbol::Empty)),
+        '#' => Ok(Some(Symbol::Wall)),
+        '^' => Ok(Some(Symbol::Blizzard(Direction::Up))),
+        '>' => Ok(Some(Symbol::Blizzard(Direction::Right))),
+        '<' => Ok(Some(Symbol::Blizzard(Direction::Left))),
+        'v' => Ok(Some(Symbol::Blizzard(Direction::Down))),
+        _ => Err(String::from("Invalid valley map character (expected one of \"#.^>v<\")"))
+    })?;
+
+    // Only openings in top and bottom walls
+    let (start, goal) = match valley_map.get_locations_with_value(&Symbol::Empty)[..] {
+        [start, .., goal] => (start, goal),
+        _ => return Err(ParseError::at_end_of_input(data, "Missing openings \".\" in top and bottom walls"))
+    };
 
-fn map_blizzard_locations(map: &Grid<isize, Symbol>) -> HashMap<Coordinate<isize>, Vec<Symbol>> {
-    let blizzard_locations = map.get_locations_with_values(vec![
-        &Symbol::Blizzard(Direction::Up),
-        &Symbol::Blizzard(Direction::Right),
-        &Symbol::Blizzard(Direction::Left),
-        &Symbol::Blizzard(Direction::Down),
-    ]);
-
-    let mut blizzard_locations_map: HashMap<Coordinate<isize>, Vec<Symbol>> = HashMap::new();
-    for location in blizzard_locations.into_iter() {
-        if blizzard_locations_map.contains_key(&location) == false {
-            blizzard_locations_map.insert(location.clone(), vec![]);
-        }
-        let value = match map.get_value(&location).unwrap() {
-            Symbol::Blizzard(Direction::Up) => Symbol::Blizzard(Direction::Up),
-            Symbol::Blizzard(Direction::Right) => Symbol::Blizzard(Direction::Right),
-            Symbol::Blizzard(Direction::Left) => Symbol::Blizzard(Direction::Left),
-            Symbol::Blizzard(Direction::Down) => Symbol::Blizzard(Direction::Down),
-            _ => panic!("Non blizzard value...")
-        };
-        blizzard_locations_map.get_mut(&location).unwrap().push(value);
-    }
+    Ok((start, goal, valley_map))
+}
 
-    blizzard_locations_map
+fn map_blizzard_locations(map: &Grid<isize, Symbol>) -> Grid<isize, Vec<Symbol>> {
+    map.iter()
+        .filter(|(_, value)| matches!(value, Symbol::Blizzard(_)))
+        .map(|(location, value)| (*location, vec![value.clone()]))
+        .collect()
 }
 
 fn find_shortest_path(
     start: &Coordinate<isize>,
     goal: &Coordinate<isize>,
     map: &mut Grid<isize, Symbol>,
-    blizzard_locations_map: &mut HashMap<Coordinate<isize>, Vec<Symbol>>
+    blizzard_locations_map: &mut Grid<isize, Vec<Symbol>>
 ) -> Option<Path<isize>> {
     let mut visited_locations: HashSet<(Coordinate<isize>, usize)> = HashSet::new();
     let mut paths_to_explored: Vec<Path<isize>> = vec![Path::new(start)];
 
+    // Blizzards are back to the same locations after a full cycle, so being at the same location at the same step of the cycle again
+    // can't lead to a better solution (and search ends when goal can't be reached)
+    let blizzard_cycle = num::integer::lcm(map.width() - 2, map.height() - 2).max(1) as usize;
+
     let mut path_size = 0;
     let mut distance_to_goal;
     while paths_to_explored.len() > 0 {
         path_size += 1;
         distance_to_goal = paths_to_explored.iter().map(|p| p.locations.last().unwrap().manhattan_distance(goal)).min().unwrap();
-        println!("Path size: {:3}; distance to goal {:3} --> nb paths to explore {}", path_size, distance_to_goal, paths_to_explored.len());
+        trace!("Path size: {:3}; distance to goal {:3} --> nb paths to explore {}", path_size, distance_to_goal, paths_to_explored.len());
 
         // Updating blizzard locations
-        let blizzard_locations: HashMap<Coordinate<isize>, Vec<Symbol>> = blizzard_locations_map.drain().collect();
+        let blizzard_locations = std::mem::take(blizzard_locations_map);
         for (location, directions) in blizzard_locations.iter() {
             for blizzard in directions.iter() {
                 // Clearing previous blizzard location
@@ -183,10 +159,7 @@ fn find_shortest_path(
                             new_location = find_looping_location(&new_location, direction, map);
                         }
 
-                        if blizzard_locations_map.contains_key(&new_location) == false {
-                            blizzard_locations_map.insert(new_location.clone(), vec![]);
-                        }
-                        blizzard_locations_map.get_mut(&new_location).unwrap().push(blizzard.clone());
+                        blizzard_locations_map.entry(new_location).or_default().push(blizzard.clone());
                     },
                     _ => {}
                 }
@@ -211,16 +184,17 @@ fn find_shortest_path(
             let mut next_locations: Vec<Coordinate<isize>> = adjacent_locations
                 .into_iter()
                 // We must allow moving back to previously visited locations to avoid blizzards...
-                // ...but we should still prune duplicate path ending at the same location after n steps, since they won't lead to a better solution
-                .filter(|l| visited_locations.contains(&(l.clone(), nb_visited_locations + 1)) == false)
+                // ...but we should still prune duplicate path ending at the same location at the same step of blizzard cycle, since they won't lead to a better solution
+                .filter(|l| visited_locations.contains(&(l.clone(), (nb_visited_locations + 1) % blizzard_cycle)) == false)
                 // Removing locations outside map or wall or blizzard location
                 .filter(|l| match map.get_value(&l) {
                     None | Some(Symbol::Wall) => false,
-                    _ => blizzard_locations_map.contains_key(&l) == false
+                    _ => !blizzard_locations_map.contains_location(l)
                 })
                 .collect();
 
-            if blizzard_locations_map.contains_key(&current_location) == false {
+            let next_step = (*current_location, (nb_visited_locations + 1) % blizzard_cycle);
+            if !blizzard_locations_map.contains_location(current_location) && !visited_locations.contains(&next_step) {
                 // Waiting for blizzard to clear (non moving step)
                 next_locations.push(current_location.clone());
             }
@@ -234,7 +208,7 @@ fn find_shortest_path(
                     return Some(next_path);
                 }
 
-                visited_locations.insert((location, next_path.locations.len()));
+                visited_locations.insert((location, next_path.locations.len() % blizzard_cycle));
                 paths_to_explored.push(next_path);
             }
         }
@@ -295,12 +269,30 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(18, solution1(data));
+        assert_eq!(18, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!(54, solution2(data));
+        assert_eq!(54, solution2(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let data = read_test_file().replace(".", "#");
+        let error = read_valley_map(&data).err().unwrap();
+        assert_eq!((6, 9, "Missing openings \".\" in top and bottom walls"), (error.line_number, error.column, &error.message[..]));
+
+        let data = read_test_file().replace("#>>.<^<#", "#>>.<^x#");
+        let error = read_valley_map(&data).err().unwrap();
+        assert_eq!((2, 7, "Invalid valley map character (expected one of \"#.^>v<\")"), (error.line_number, error.column, &error.message[..]));
+    }
+
+    #[test]
+    fn test_unreachable_goal() {
+        let data = "#.###\n#.#.#\n###.#\n";
+        assert!(matches!(solution1(data, &Params::new()), Err(SolveError::NoSolution(_))));
+        assert!(matches!(solution2(data, &Params::new()), Err(SolveError::NoSolution(_))));
     }
 }
@@ -1,29 +1,34 @@
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::parsing::ParseError;
+use crate::info;
 use itertools::Itertools;
 
-pub fn solution1(data: String) -> String {
-    let total: usize = data.lines().map(|line| snafu_to_decimal(line)).sum();
+pub fn solution1(data: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let total: usize = data.lines().enumerate().map(|(idx, line)| snafu_to_decimal(idx, line)).sum::<Result<usize, ParseError>>()?;
     let result = decimal_to_snafu(&total);
 
-    println!("=========================");
-    println!("Total: {total}");
-    println!("=========================");
-    println!("Solution1: {result}");
-    println!("=========================");
+    info!("=========================");
+    info!("Total: {total}");
 
-    result
+    Ok(Answer::from(result))
 }
 
-pub fn solution2(_data: String) {
-    println!("*** Merry Christmas ***");
+pub fn solution2(_data: &str, _params: &Params) -> Result<Answer, SolveError> {
+    info!("*** Merry Christmas ***");
+
+    Ok(Answer::Empty)
 }
 
 /////////////////////////////////////////////////
 
 const SNAFU_BASE: usize = 5;
 
-fn snafu_to_decimal(snafu_number: &str) -> usize {
+/// Decimal value of SNAFU number line
+fn snafu_to_decimal(line_idx: usize, snafu_number: &str) -> Result<usize, ParseError> {
     let mut number: usize = 0;
 
+    let nb_chars = snafu_number.chars().count();
     for (idx, char) in snafu_number.chars().rev().enumerate() {
         let multiplier: isize = SNAFU_BASE.pow(idx as u32).try_into().unwrap();
         let value: isize = match char {
@@ -32,13 +37,13 @@ fn snafu_to_decimal(snafu_number: &str) -> usize {
             '0' => 0 * multiplier,
             '-' => -1 * multiplier,
             '=' => -2 * multiplier,
-            _ => panic!("Invalid snafu character {char}")
+            _ => return Err(ParseError::new(line_idx, snafu_number, nb_chars - 1 - idx, 1, "Invalid SNAFU digit (expected one of \"210-=\")"))
         };
 
         number = (number as isize + value) as usize;
     }
 
-    number
+    Ok(number)
 }
 
 fn decimal_to_snafu(number: &usize) -> String {
@@ -81,12 +86,20 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!(String::from("2=-1=0"), solution1(data));
+        assert_eq!(String::from("2=-1=0"), solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!((), solution2(data));
+        assert_eq!((), solution2(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let data = read_test_file().replacen("12111", "12131", 1);
+        let error = snafu_to_decimal(1, data.lines().nth(1).unwrap()).unwrap_err();
+        assert_eq!((2, 4, "Invalid SNAFU digit (expected one of \"210-=\")"), (error.line_number, error.column, &error.message[..]));
+        assert!(matches!(solution1(&data, &Params::new()), Err(SolveError::Parse(_))));
     }
 }
@@ -0,0 +1,30 @@
+/// Solutions year
+pub const YEAR: u16 = 2022;
+
+register_solutions! {
+    day01 => day01::get_values,
+    day02,
+    day03,
+    day04,
+    day05 => day05::read_stacks_and_moves,
+    day06,
+    day07 => day07::read_filesystem,
+    day08 => day08::read_tree_map,
+    day09 => day09::read_moves,
+    day10 => crate::circuit::read_instructions,
+    day11 => day11::read_monkey_data,
+    day12 => day12::read_elevation_map,
+    day13 => day13::read_packets,
+    day14 => day14::read_cave_map,
+    day15 => day15::read_sensors_data,
+    day16 => day16::read_network_data,
+    day17 => day17::read_jet_patterns,
+    day18 => day18::read_cubes,
+    day19 => day19::read_blueprints,
+    day20 => day20::read_values,
+    day21,
+    day22 => day22::read_board_map_data,
+    day23 => day23::read_grove_map,
+    day24 => day24::read_valley_map,
+    day25
+}
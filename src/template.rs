@@ -0,0 +1,73 @@
+/// Available solution templates (in `templates` folder)
+pub const TEMPLATE_NAMES: &[&str] = &["default", "circuit", "grid", "grid3d", "parser", "search"];
+
+/// Input parser defined by template (registered with solution, for benchmarks)
+pub fn template_parser(template: &str) -> Option<&'static str> {
+    match template {
+        "grid" | "grid3d" | "search" => Some("read_map"),
+        "circuit" => Some("read_program"),
+        "parser" => Some("read_items"),
+        _ => None
+    }
+}
+
+/// Values substituted into solution template placeholders (e.g: `{{title}}`)
+pub struct TemplateValues<'a> {
+    pub day: &'a str,
+    pub title: Option<&'a str>,
+    pub expected_answers: [Option<&'a str>; 2]
+}
+
+/// Replace template placeholders: `{{day}}` (e.g: `day05`), `{{day_number}}` (e.g: `5`), `{{title}}`, `{{expected1}}` and `{{expected2}}`
+pub fn render_template(template: &str, values: &TemplateValues) -> String {
+    let day_number = match values.day.trim_start_matches("day").parse::<u32>() {
+        Ok(n) => n.to_string(),
+        Err(_) => String::from(values.day)
+    };
+
+    // Title is inserted in a doc comment line, so it must stay on a single line
+    let title = match values.title.map(|title| title.split_whitespace().collect::<Vec<&str>>().join(" ")) {
+        Some(title) if !title.is_empty() => title,
+        _ => String::from("Untitled")
+    };
+
+    template
+        .replace("{{day}}", values.day)
+        .replace("{{day_number}}", &day_number)
+        .replace("{{title}}", &title)
+        .replace("{{expected1}}", &answer_literal(values.expected_answers[0]))
+        .replace("{{expected2}}", &answer_literal(values.expected_answers[1]))
+}
+
+/// Rust literal comparable with an answer: number, string or `()` when unknown
+fn answer_literal(answer: Option<&str>) -> String {
+    match answer {
+        Some(answer) if answer.parse::<i64>().is_ok() => String::from(answer),
+        Some(answer) => format!("{:?}", answer),
+        None => String::from("()")
+    }
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let template = "//! --- Day {{day_number}}: {{title}} ---\n// {{day}}\nassert_eq!({{expected1}}, a);\nassert_eq!({{expected2}}, b);";
+
+        let values = TemplateValues { day: "day05", title: Some("Supply Stacks"), expected_answers: [Some("CMZ"), Some("-12")] };
+        assert_eq!("//! --- Day 5: Supply Stacks ---\n// day05\nassert_eq!(\"CMZ\", a);\nassert_eq!(-12, b);", render_template(template, &values));
+
+        let values = TemplateValues { day: "day12", title: None, expected_answers: [None, None] };
+        assert_eq!("//! --- Day 12: Untitled ---\n// day12\nassert_eq!((), a);\nassert_eq!((), b);", render_template(template, &values));
+
+        // Single line title
+        let values = TemplateValues { day: "day07", title: Some("No Space\n Left\r\nOn Device "), expected_answers: [None, None] };
+        assert!(render_template(template, &values).starts_with("//! --- Day 7: No Space Left On Device ---\n// day07\n"));
+        let values = TemplateValues { day: "day07", title: Some("\n"), expected_answers: [None, None] };
+        assert!(render_template(template, &values).starts_with("//! --- Day 7: Untitled ---\n"));
+    }
+}
@@ -0,0 +1,61 @@
+//! --- Day {{day_number}}: {{title}} ---
+use crate::answer::{Answer, SolveError};
+use crate::circuit::{read_instructions, ClockCircuit, Instruction};
+use crate::debug;
+use crate::params::Params;
+use crate::parsing::ParseError;
+
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_program(data)?, params)
+}
+
+pub(crate) fn solve1(instructions: Vec<Instruction>, _params: &Params) -> Result<Answer, SolveError> {
+    let mut circuit = ClockCircuit::new();
+    circuit.run(&instructions);
+
+    let result = circuit.get_signal_strengh(None);
+
+    Ok(Answer::from(result))
+}
+
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_program(data)?, params)
+}
+
+pub(crate) fn solve2(instructions: Vec<Instruction>, _params: &Params) -> Result<Answer, SolveError> {
+    debug!("{} instructions", instructions.len());
+
+    Ok(Answer::Empty)
+}
+
+/////////////////////////////////////////////////
+
+/// Clock circuit program, one instruction per line (e.g: `noop` or `addx -5`)
+pub(crate) fn read_program(data: &str) -> Result<Vec<Instruction>, ParseError> {
+    read_instructions(data)
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_test_file() -> String {
+        let current_file = std::file!();
+        let test_file = current_file.replace("mod.rs", "test.txt");
+        return crate::read_file(&test_file).unwrap();
+    }
+
+    #[test]
+    fn test_solution1() {
+        let data = read_test_file();
+        assert_eq!({{expected1}}, solution1(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_solution2() {
+        let data = read_test_file();
+        assert_eq!({{expected2}}, solution2(&data, &Params::new()).unwrap());
+    }
+}
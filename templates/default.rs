@@ -1,17 +1,18 @@
-pub fn solution1(data: String) {
-    println!("{}", data);
+//! --- Day {{day_number}}: {{title}} ---
+use crate::answer::{Answer, SolveError};
+use crate::params::Params;
+use crate::debug;
 
-    println!("=========================");
-    println!("Solution1: ");
-    println!("=========================");
+pub fn solution1(data: &str, _params: &Params) -> Result<Answer, SolveError> {
+    debug!("{}", data);
+
+    Ok(Answer::Empty)
 }
 
-pub fn solution2(data: String) {
-    println!("{}", data);
+pub fn solution2(data: &str, _params: &Params) -> Result<Answer, SolveError> {
+    debug!("{}", data);
 
-    println!("=========================");
-    println!("Solution2: ");
-    println!("=========================");
+    Ok(Answer::Empty)
 }
 
 /////////////////////////////////////////////////
@@ -29,12 +30,12 @@ mod tests {
     #[test]
     fn test_solution1() {
         let data = read_test_file();
-        assert_eq!((), solution1(data));
+        assert_eq!({{expected1}}, solution1(&data, &Params::new()).unwrap());
     }
 
     #[test]
     fn test_solution2() {
         let data = read_test_file();
-        assert_eq!((), solution2(data));
+        assert_eq!({{expected2}}, solution2(&data, &Params::new()).unwrap());
     }
 }
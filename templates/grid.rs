@@ -0,0 +1,76 @@
+//! --- Day {{day_number}}: {{title}} ---
+use crate::answer::{Answer, SolveError};
+use crate::grid::GridLike;
+use crate::navigation::{Grid, GridMap};
+use crate::params::Params;
+use crate::parsing::ParseError;
+
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_map(data)?, params)
+}
+
+pub(crate) fn solve1(map: Grid<isize, Symbol>, _params: &Params) -> Result<Answer, SolveError> {
+    map.print(&Symbol::Empty, &symbol_to_char);
+
+    let result = map.count_values(&Symbol::Wall);
+
+    Ok(Answer::from(result))
+}
+
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_map(data)?, params)
+}
+
+pub(crate) fn solve2(map: Grid<isize, Symbol>, _params: &Params) -> Result<Answer, SolveError> {
+    map.print(&Symbol::Empty, &symbol_to_char);
+
+    Ok(Answer::Empty)
+}
+
+/////////////////////////////////////////////////
+
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum Symbol {
+    Wall,
+    Empty
+}
+
+fn symbol_to_char(symbol: &Symbol) -> &str {
+    match symbol {
+        Symbol::Wall => "#",
+        Symbol::Empty => "."
+    }
+}
+
+pub(crate) fn read_map(data: &str) -> Result<Grid<isize, Symbol>, ParseError> {
+    Grid::try_parse(data, |char| match char {
+        '#' => Ok(Some(Symbol::Wall)),
+        '.' => Ok(Some(Symbol::Empty)),
+        _ => Err(String::from("Invalid map character (expected \"#\" or \".\")"))
+    })
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_test_file() -> String {
+        let current_file = std::file!();
+        let test_file = current_file.replace("mod.rs", "test.txt");
+        return crate::read_file(&test_file).unwrap();
+    }
+
+    #[test]
+    fn test_solution1() {
+        let data = read_test_file();
+        assert_eq!({{expected1}}, solution1(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_solution2() {
+        let data = read_test_file();
+        assert_eq!({{expected2}}, solution2(&data, &Params::new()).unwrap());
+    }
+}
@@ -0,0 +1,84 @@
+//! --- Day {{day_number}}: {{title}} ---
+use crate::answer::{Answer, SolveError};
+use crate::debug;
+use crate::grid::GridLike;
+use crate::navigation3d::{Coordinate3D, Grid3D};
+use crate::params::Params;
+use crate::parsing::{parse_value, ParseError};
+
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_map(data)?, params)
+}
+
+pub(crate) fn solve1(map: Grid3D<isize, Symbol>, _params: &Params) -> Result<Answer, SolveError> {
+    map.print(&Symbol::Empty, &symbol_to_char);
+
+    let result = map.count_values(&Symbol::Cube);
+
+    Ok(Answer::from(result))
+}
+
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_map(data)?, params)
+}
+
+pub(crate) fn solve2(map: Grid3D<isize, Symbol>, _params: &Params) -> Result<Answer, SolveError> {
+    debug!("{} x {} x {} space", map.width(), map.height(), map.depth());
+
+    Ok(Answer::Empty)
+}
+
+/////////////////////////////////////////////////
+
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum Symbol {
+    Cube,
+    Empty
+}
+
+fn symbol_to_char(symbol: &Symbol) -> &str {
+    match symbol {
+        Symbol::Cube => "#",
+        Symbol::Empty => "."
+    }
+}
+
+pub(crate) fn read_map(data: &str) -> Result<Grid3D<isize, Symbol>, ParseError> {
+    data.lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            let values: Vec<&str> = line.split(',').collect();
+            if values.len() != 3 {
+                return Err(ParseError::at_line(idx, line, "Invalid location (expected \"x,y,z\")"));
+            }
+
+            let location = Coordinate3D { x: parse_value(idx, line, values[0])?, y: parse_value(idx, line, values[1])?, z: parse_value(idx, line, values[2])? };
+            Ok((location, Symbol::Cube))
+        })
+        .collect()
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_test_file() -> String {
+        let current_file = std::file!();
+        let test_file = current_file.replace("mod.rs", "test.txt");
+        return crate::read_file(&test_file).unwrap();
+    }
+
+    #[test]
+    fn test_solution1() {
+        let data = read_test_file();
+        assert_eq!({{expected1}}, solution1(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_solution2() {
+        let data = read_test_file();
+        assert_eq!({{expected2}}, solution2(&data, &Params::new()).unwrap());
+    }
+}
@@ -0,0 +1,75 @@
+//! --- Day {{day_number}}: {{title}} ---
+use crate::answer::{Answer, SolveError};
+use crate::debug;
+use crate::params::Params;
+use crate::parsing::{parse_value, ParseError};
+use regex::Regex;
+
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_items(data)?, params)
+}
+
+pub(crate) fn solve1(items: Vec<Item>, _params: &Params) -> Result<Answer, SolveError> {
+    for item in items.iter() {
+        debug!("{} --> {}", item.name, item.value);
+    }
+
+    let result: isize = items.iter().map(|i| i.value).sum();
+
+    Ok(Answer::from(result))
+}
+
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_items(data)?, params)
+}
+
+pub(crate) fn solve2(items: Vec<Item>, _params: &Params) -> Result<Answer, SolveError> {
+    debug!("{} items", items.len());
+
+    Ok(Answer::Empty)
+}
+
+/////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub(crate) struct Item {
+    name: String,
+    value: isize
+}
+
+pub(crate) fn read_items(data: &str) -> Result<Vec<Item>, ParseError> {
+    let item_regex = Regex::new(r"^(\w+): (-?\d+)$").unwrap();
+
+    data.lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            let captures = item_regex.captures(line).ok_or_else(|| ParseError::at_line(idx, line, "Invalid item (expected \"name: value\")"))?;
+            Ok(Item { name: String::from(&captures[1]), value: parse_value::<isize>(idx, line, &captures[2])? })
+        })
+        .collect()
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_test_file() -> String {
+        let current_file = std::file!();
+        let test_file = current_file.replace("mod.rs", "test.txt");
+        return crate::read_file(&test_file).unwrap();
+    }
+
+    #[test]
+    fn test_solution1() {
+        let data = read_test_file();
+        assert_eq!({{expected1}}, solution1(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_solution2() {
+        let data = read_test_file();
+        assert_eq!({{expected2}}, solution2(&data, &Params::new()).unwrap());
+    }
+}
@@ -0,0 +1,102 @@
+//! --- Day {{day_number}}: {{title}} ---
+use crate::answer::{Answer, SolveError};
+use crate::grid::{GridLike, LocationValidator};
+use crate::info;
+use crate::navigation::{find_shortest_path, Coordinate, Grid, GridMap, PathTarget};
+use crate::params::Params;
+use crate::parsing::ParseError;
+
+pub fn solution1(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve1(read_map(data)?, params)
+}
+
+pub(crate) fn solve1((start, goal, map): SearchMap, _params: &Params) -> Result<Answer, SolveError> {
+    map.print(&Symbol::Empty, &symbol_to_char);
+    info!("Start: {:?}", start);
+    info!("Goal: {:?}", goal);
+
+    // Finding shortest path from start to goal
+    let path = match find_shortest_path(&map, &start, &PathTarget::Location(goal), Some(build_location_validator())) {
+        Some(path) => path,
+        None => return Err(SolveError::NoSolution(String::from("No path found")))
+    };
+    let result = path.locations.len() - 1; // Excluding starting position
+
+    Ok(Answer::from(result))
+}
+
+pub fn solution2(data: &str, params: &Params) -> Result<Answer, SolveError> {
+    solve2(read_map(data)?, params)
+}
+
+pub(crate) fn solve2((_, _, map): SearchMap, _params: &Params) -> Result<Answer, SolveError> {
+    map.print(&Symbol::Empty, &symbol_to_char);
+
+    Ok(Answer::Empty)
+}
+
+/////////////////////////////////////////////////
+
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum Symbol {
+    Start,
+    Goal,
+    Wall,
+    Empty
+}
+
+fn symbol_to_char(symbol: &Symbol) -> &str {
+    match symbol {
+        Symbol::Start => "S",
+        Symbol::Goal => "E",
+        Symbol::Wall => "#",
+        Symbol::Empty => "."
+    }
+}
+
+/// Start and goal locations, with map
+type SearchMap = (Coordinate<isize>, Coordinate<isize>, Grid<isize, Symbol>);
+
+pub(crate) fn read_map(data: &str) -> Result<SearchMap, ParseError> {
+    let map = Grid::try_parse(data, |char| match char {
+        'S' => Ok(Some(Symbol::Start)),
+        'E' => Ok(Some(Symbol::Goal)),
+        '#' => Ok(Some(Symbol::Wall)),
+        '.' => Ok(Some(Symbol::Empty)),
+        _ => Err(String::from("Invalid map character (expected one of \"SE#.\")"))
+    })?;
+
+    let start = *map.get_mapped_locations_with_value(&Symbol::Start).first().ok_or_else(|| ParseError::at_end_of_input(data, "Missing start location \"S\""))?;
+    let goal = *map.get_mapped_locations_with_value(&Symbol::Goal).first().ok_or_else(|| ParseError::at_end_of_input(data, "Missing goal location \"E\""))?;
+
+    Ok((start, goal, map))
+}
+
+fn build_location_validator() -> LocationValidator<Symbol> {
+    Box::new(|_current: &Symbol, next: &Symbol| -> bool { *next != Symbol::Wall })
+}
+
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_test_file() -> String {
+        let current_file = std::file!();
+        let test_file = current_file.replace("mod.rs", "test.txt");
+        return crate::read_file(&test_file).unwrap();
+    }
+
+    #[test]
+    fn test_solution1() {
+        let data = read_test_file();
+        assert_eq!({{expected1}}, solution1(&data, &Params::new()).unwrap());
+    }
+
+    #[test]
+    fn test_solution2() {
+        let data = read_test_file();
+        assert_eq!({{expected2}}, solution2(&data, &Params::new()).unwrap());
+    }
+}